@@ -0,0 +1,229 @@
+use super::config::api_url;
+use super::error::ApiError;
+use super::parse;
+use super::query::{ContentQuery, PagedContent};
+use crate::dto::{
+    AccessTokenDto, ContentDto, DiscoveredFeedDto, FeedPreviewDto, LinkCreatedDto, LinkDto,
+    LinkUpdateDto, UnreadCountDto,
+};
+use crate::router::Route;
+use crate::store::UserStore;
+use reqwasm::http::Request;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use yew_router::history::{BrowserHistory, History};
+use yewdux::dispatch::{Dispatch, Dispatcher};
+use yewdux::prelude::BasicStore;
+
+const SESSION_EXPIRED: &str = "Your session has expired, please sign in again";
+
+/// Client for the authenticated part of the backend.
+///
+/// Adds the bearer token of the current `UserStore` to every request and
+/// signs the user out when the backend answers with 401.
+#[derive(Clone)]
+pub struct ApiClient {
+    token: String,
+    dispatch: Dispatch<BasicStore<UserStore>>,
+}
+
+impl PartialEq for ApiClient {
+    fn eq(&self, other: &Self) -> bool {
+        self.token == other.token
+    }
+}
+
+impl ApiClient {
+    pub fn new(state: &UserStore) -> Self {
+        Self {
+            token: state.token.clone(),
+            dispatch: Dispatch::new(),
+        }
+    }
+
+    pub async fn get_links(&self) -> Result<Vec<LinkDto>, ApiError> {
+        self.send(Request::get(&api_url("/link"))).await
+    }
+
+    /// Reads the size of the stream from the `X-Total-Count` header when present.
+    pub async fn get_content(&self, query: &ContentQuery) -> Result<PagedContent, ApiError> {
+        let request = Request::get(&api_url(&format!("/content?{}", query.to_query_string())));
+        let res = self.authorize(request).send().await?;
+        let total = res
+            .headers()
+            .get("x-total-count")
+            .ok()
+            .flatten()
+            .and_then(|total| total.trim().parse().ok());
+        let result = parse(res).await.map(|items| PagedContent { items, total });
+        self.intercept(result)
+    }
+
+    pub async fn get_content_item(&self, content_id: &str) -> Result<ContentDto, ApiError> {
+        self.send(Request::get(&api_url(&format!(
+            "/content/{}",
+            js_sys::encode_uri_component(content_id)
+        ))))
+        .await
+    }
+
+    /// Full-text search, `query` without the operators the client handles.
+    pub async fn search(
+        &self,
+        query: &str,
+        link_ids: &[String],
+        unread_only: bool,
+    ) -> Result<Vec<ContentDto>, ApiError> {
+        let mut url = format!("/content/search?q={}", js_sys::encode_uri_component(query));
+        if unread_only {
+            url.push_str("&unread=true");
+        }
+        for link_id in link_ids {
+            url.push_str(&format!(
+                "&link_id={}",
+                js_sys::encode_uri_component(link_id)
+            ));
+        }
+        self.send(Request::get(&api_url(&url))).await
+    }
+
+    pub async fn get_unread_counts(&self) -> Result<Vec<UnreadCountDto>, ApiError> {
+        self.send(Request::get(&api_url("/content/unread"))).await
+    }
+
+    pub async fn mark_read(&self, content_ids: Vec<String>) -> Result<(), ApiError> {
+        let body = json!({
+            "content_ids": content_ids,
+        });
+        self.execute(
+            Request::post(&api_url("/content/read"))
+                .header("Content-Type", "application/json")
+                .body(body.to_string()),
+        )
+        .await
+    }
+
+    pub async fn mark_unread(&self, content_ids: Vec<String>) -> Result<(), ApiError> {
+        let body = json!({
+            "content_ids": content_ids,
+        });
+        self.execute(
+            Request::delete(&api_url("/content/read"))
+                .header("Content-Type", "application/json")
+                .body(body.to_string()),
+        )
+        .await
+    }
+
+    /// Marks the whole stream as read, or only items published before `before`
+    /// (an ISO 8601 date). Limited to the feeds `link_ids` unless that is empty.
+    pub async fn mark_all_read(
+        &self,
+        before: Option<String>,
+        link_ids: Vec<String>,
+    ) -> Result<(), ApiError> {
+        let mut body = json!({
+            "before": before,
+        });
+        if !link_ids.is_empty() {
+            body["link_ids"] = json!(link_ids);
+        }
+        self.execute(
+            Request::post(&api_url("/content/read-all"))
+                .header("Content-Type", "application/json")
+                .body(body.to_string()),
+        )
+        .await
+    }
+
+    pub async fn create_link(&self, link_url: String) -> Result<LinkCreatedDto, ApiError> {
+        let body = json!({
+            "link": link_url,
+        });
+        self.send(
+            Request::post(&api_url("/link"))
+                .header("Content-Type", "application/json")
+                .body(body.to_string()),
+        )
+        .await
+    }
+
+    /// Lets the backend fetch and parse `url` without subscribing to it.
+    pub async fn preview_feed(&self, url: String) -> Result<FeedPreviewDto, ApiError> {
+        self.send(Request::get(&api_url(&format!(
+            "/link/preview?url={}",
+            js_sys::encode_uri_component(&url)
+        ))))
+        .await
+    }
+
+    pub async fn update_link(
+        &self,
+        link_id: String,
+        update: &LinkUpdateDto,
+    ) -> Result<LinkDto, ApiError> {
+        let body = serde_json::to_string(update).map_err(|e| ApiError::Decode(e.to_string()))?;
+        self.send(
+            Request::patch(&api_url(&format!("/link/{}", link_id)))
+                .header("Content-Type", "application/json")
+                .body(body),
+        )
+        .await
+    }
+
+    /// Lets the backend look for feeds advertised by the page at `url`.
+    pub async fn discover_feeds(&self, url: String) -> Result<Vec<DiscoveredFeedDto>, ApiError> {
+        self.send(Request::get(&api_url(&format!(
+            "/link/discover?url={}",
+            js_sys::encode_uri_component(&url)
+        ))))
+        .await
+    }
+
+    pub async fn remove_link(&self, link_id: String) -> Result<bool, ApiError> {
+        self.send(Request::delete(&api_url(&format!("/link/{}", link_id))))
+            .await
+    }
+
+    /// Exchanges the current, still valid token for a fresh one.
+    pub async fn refresh_token(&self) -> Result<AccessTokenDto, ApiError> {
+        self.send(Request::post(&api_url("/refresh"))).await
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: Request) -> Result<T, ApiError> {
+        let res = self.authorize(request).send().await?;
+        let result = parse(res).await;
+        self.intercept(result)
+    }
+
+    /// Like `send` for endpoints that answer without a body.
+    async fn execute(&self, request: Request) -> Result<(), ApiError> {
+        let res = self.authorize(request).send().await?;
+        let result = if res.ok() {
+            Ok(())
+        } else {
+            Err(ApiError::from_response(res).await)
+        };
+        self.intercept(result)
+    }
+
+    fn authorize(&self, request: Request) -> Request {
+        request.header("Authorization", &format!("Bearer {}", self.token))
+    }
+
+    fn intercept<T>(&self, result: Result<T, ApiError>) -> Result<T, ApiError> {
+        if let Err(ApiError::Unauthorized) = result {
+            self.expire_session();
+        }
+        result
+    }
+
+    /// Signs the user out and sends them to the sign in page.
+    pub fn expire_session(&self) {
+        self.dispatch.reduce(|s| {
+            s.sign_out();
+            s.notice = Some(SESSION_EXPIRED.to_string());
+        });
+        BrowserHistory::new().push(Route::SignIn);
+    }
+}
@@ -0,0 +1,76 @@
+use gloo_utils::document;
+use std::sync::OnceLock;
+
+const DEFAULT_BASE_URL: &str = "http://127.0.0.1:3000";
+const BASE_URL_META: &str = "rss-api-url";
+const PREFIX_META: &str = "rss-api-prefix";
+
+static CONFIG: OnceLock<ApiConfig> = OnceLock::new();
+
+/// Backend location every api call is resolved against.
+///
+/// Values are taken from the `<meta name="rss-api-url">` / `<meta name="rss-api-prefix">`
+/// tags of `index.html` first, then from the `RSS_API_URL` / `RSS_API_PREFIX` build-time
+/// env vars, and finally fall back to the local dev backend.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiConfig {
+    pub base_url: String,
+    pub prefix: String,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            prefix: "".to_string(),
+        }
+    }
+}
+
+impl ApiConfig {
+    pub fn load() -> Self {
+        let default = Self::default();
+        let base_url = meta_content(BASE_URL_META)
+            .or_else(|| option_env!("RSS_API_URL").map(str::to_string))
+            .unwrap_or(default.base_url);
+        let prefix = meta_content(PREFIX_META)
+            .or_else(|| option_env!("RSS_API_PREFIX").map(str::to_string))
+            .unwrap_or(default.prefix);
+        Self { base_url, prefix }
+    }
+
+    /// Joins `path` (e.g. `/link/42`) onto the base url and prefix.
+    pub fn url(&self, path: &str) -> String {
+        let base = self.base_url.trim_end_matches('/');
+        let prefix = self.prefix.trim_matches('/');
+        let path = path.trim_start_matches('/');
+        if prefix.is_empty() {
+            format!("{}/{}", base, path)
+        } else {
+            format!("{}/{}/{}", base, prefix, path)
+        }
+    }
+}
+
+/// Loads the config once; must be called before the app is started.
+pub fn init_config() {
+    CONFIG.get_or_init(ApiConfig::load);
+}
+
+pub fn config() -> &'static ApiConfig {
+    CONFIG.get_or_init(ApiConfig::load)
+}
+
+pub(super) fn api_url(path: &str) -> String {
+    config().url(path)
+}
+
+fn meta_content(name: &str) -> Option<String> {
+    document()
+        .query_selector(&format!("meta[name=\"{}\"]", name))
+        .ok()
+        .flatten()
+        .and_then(|meta| meta.get_attribute("content"))
+        .map(|content| content.trim().to_string())
+        .filter(|content| !content.is_empty())
+}
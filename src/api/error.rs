@@ -0,0 +1,113 @@
+use reqwasm::http::Response;
+use reqwasm::Error;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Error body returned by the backend for non-2xx responses.
+///
+/// `message` is a plain string for most errors and a list of messages for
+/// validation failures.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct SignError {
+    #[serde(alias = "statusCode", default)]
+    pub status: u16,
+    #[serde(default)]
+    pub message: ErrorMessage,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ErrorMessage {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Default for ErrorMessage {
+    fn default() -> Self {
+        ErrorMessage::Many(vec![])
+    }
+}
+
+impl ErrorMessage {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            ErrorMessage::One(message) => vec![message],
+            ErrorMessage::Many(messages) => messages,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ApiError {
+    Unauthorized,
+    NotFound,
+    Conflict(String),
+    Validation(Vec<String>),
+    Server { status: u16, message: String },
+    Network(String),
+    Decode(String),
+}
+
+impl ApiError {
+    /// Builds the error for a response whose status is not 2xx.
+    pub async fn from_response(res: Response) -> Self {
+        let status = res.status();
+        let messages = match res.json::<SignError>().await {
+            Ok(body) => body.message.into_vec(),
+            Err(_) => vec![],
+        };
+        Self::from_status(status, messages, res.status_text())
+    }
+
+    /// True when the backend does not implement the endpoint that was called.
+    pub fn is_unsupported(&self) -> bool {
+        matches!(
+            self,
+            ApiError::NotFound
+                | ApiError::Server {
+                    status: 405 | 501,
+                    ..
+                }
+        )
+    }
+
+    fn from_status(status: u16, messages: Vec<String>, status_text: String) -> Self {
+        let message = if messages.is_empty() {
+            status_text
+        } else {
+            messages.join(", ")
+        };
+        match status {
+            401 => ApiError::Unauthorized,
+            404 => ApiError::NotFound,
+            409 => ApiError::Conflict(message),
+            400 | 422 if !messages.is_empty() => ApiError::Validation(messages),
+            _ => ApiError::Server { status, message },
+        }
+    }
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::JsError(error) => ApiError::Network(error.message),
+            Error::SerdeError(error) => ApiError::Decode(error.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Unauthorized => write!(f, "Not signed in or session expired"),
+            ApiError::NotFound => write!(f, "Not found"),
+            ApiError::Conflict(message) => write!(f, "Already exists: {}", message),
+            ApiError::Validation(messages) => write!(f, "{}", messages.join(", ")),
+            ApiError::Server { status, message } => {
+                write!(f, "Server error {}: {}", status, message)
+            }
+            ApiError::Network(message) => write!(f, "Network error: {}", message),
+            ApiError::Decode(message) => write!(f, "Unexpected response: {}", message),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use crate::dto::ContentDto;
+
+/// Filters and paging of the `/content` stream.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContentQuery {
+    pub start: u32,
+    pub take: u32,
+    pub unread_only: bool,
+    /// Only items of these feeds; all feeds when empty.
+    pub link_ids: Vec<String>,
+}
+
+/// One page of the `/content` stream.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PagedContent {
+    pub items: Vec<ContentDto>,
+    /// Size of the whole stream, when the backend reports it.
+    pub total: Option<u32>,
+}
+
+impl Default for ContentQuery {
+    fn default() -> Self {
+        Self {
+            start: 0,
+            take: 15,
+            unread_only: false,
+            link_ids: vec![],
+        }
+    }
+}
+
+impl ContentQuery {
+    pub fn to_query_string(&self) -> String {
+        let mut query = format!("start={}&take={}", self.start, self.take);
+        if self.unread_only {
+            query.push_str("&unread=true");
+        }
+        for link_id in &self.link_ids {
+            query.push_str(&format!(
+                "&link_id={}",
+                js_sys::encode_uri_component(link_id)
+            ));
+        }
+        query
+    }
+}
@@ -0,0 +1,290 @@
+use crate::api::{ApiClient, ApiError};
+use crate::components::feed_icon::FeedIcon;
+use crate::components::news::SafeHtml;
+use crate::date::{absolute, parse_date, relative};
+use crate::dto::{ContentDto, LinkDto};
+use crate::router::{PageQuery, Route};
+use crate::store::{ReadStore, StarStore, StreamStore, UserStore};
+use js_sys::Date;
+use log::error;
+use std::rc::Rc;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::window;
+use yew::prelude::*;
+use yew_router::prelude::*;
+use yewdux::dispatch::{Dispatch, Dispatcher};
+use yewdux::prelude::BasicStore;
+
+pub enum ArticleMessage {
+    UserState(Rc<UserStore>),
+    StreamState(Rc<StreamStore>),
+    StarState(Rc<StarStore>),
+    Fetched(String, Result<Box<ContentDto>, ApiError>),
+    Links(Vec<LinkDto>),
+    ToggleStar,
+}
+
+#[derive(PartialEq, Properties)]
+pub struct ArticleProps {
+    pub id: String,
+}
+
+/// Reader view of a single item, with the rest of the stream a step away.
+pub struct ArticleComponent {
+    _dispatch: Dispatch<BasicStore<UserStore>>,
+    _stream_dispatch: Dispatch<BasicStore<StreamStore>>,
+    star_dispatch: Dispatch<BasicStore<StarStore>>,
+    read_dispatch: Dispatch<BasicStore<ReadStore>>,
+    state: Rc<UserStore>,
+    stream: Rc<StreamStore>,
+    star_state: Rc<StarStore>,
+    links: Vec<LinkDto>,
+    /// Shown item, `None` while it loads.
+    article: Option<ContentDto>,
+    error: Option<String>,
+}
+
+impl Component for ArticleComponent {
+    type Message = ArticleMessage;
+    type Properties = ArticleProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let dispatch = Dispatch::bridge_state(ctx.link().callback(ArticleMessage::UserState));
+        let stream_dispatch =
+            Dispatch::bridge_state(ctx.link().callback(ArticleMessage::StreamState));
+        let star_dispatch = Dispatch::bridge_state(ctx.link().callback(ArticleMessage::StarState));
+        Self {
+            _dispatch: dispatch,
+            _stream_dispatch: stream_dispatch,
+            star_dispatch,
+            read_dispatch: Dispatch::new(),
+            // Signed out until the store reports, so its first state loads the article.
+            state: Rc::new(UserStore {
+                token: String::new(),
+                ..Default::default()
+            }),
+            stream: Default::default(),
+            star_state: Default::default(),
+            links: vec![],
+            article: None,
+            error: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ArticleMessage::UserState(state) => {
+                // Token refreshes keep the article, only signing in loads it.
+                let signed_in = !self.state.has_token() && state.has_token();
+                self.state = state;
+                if signed_in {
+                    self.load(ctx);
+                    self.load_links(ctx);
+                }
+                true
+            }
+            ArticleMessage::StreamState(stream) => {
+                self.stream = stream;
+                true
+            }
+            ArticleMessage::StarState(state) => {
+                self.star_state = state;
+                true
+            }
+            ArticleMessage::Fetched(id, result) => {
+                if id != ctx.props().id {
+                    return false;
+                }
+                match result {
+                    Ok(content) => self.show(*content),
+                    Err(error) => {
+                        if self.article.is_none() {
+                            self.error = Some(error.to_string());
+                        }
+                    }
+                }
+                true
+            }
+            ArticleMessage::Links(links) => {
+                self.links = links;
+                true
+            }
+            ArticleMessage::ToggleStar => {
+                if let Some(content) = &self.article {
+                    let content = content.clone();
+                    self.star_dispatch.reduce(move |s| s.toggle(&content));
+                }
+                false
+            }
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        self.article = None;
+        self.error = None;
+        if let Some(window) = window() {
+            window.scroll_to_with_x_and_y(0.0, 0.0);
+        }
+        self.load(ctx);
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let body = match (&self.article, &self.error) {
+            (Some(content), _) => self.html_article(ctx, content),
+            (None, Some(error)) => html!(<p class="error-message">{error}</p>),
+            (None, None) => html!(<p class="reader-status">{"loading..."}</p>),
+        };
+        html!(
+            <div class="reader">
+                {self.html_nav(ctx)}
+                {body}
+                {self.html_nav(ctx)}
+            </div>
+        )
+    }
+}
+
+impl ArticleComponent {
+    /// Shows the item from the stream or the starred items at once, and asks
+    /// the backend for the full version.
+    fn load(&mut self, ctx: &Context<Self>) {
+        let id = ctx.props().id.clone();
+        let local = self.stream.find(&id).cloned().or_else(|| {
+            self.star_state
+                .items
+                .iter()
+                .find(|item| item.content.content_id == id)
+                .map(|item| item.content.clone())
+        });
+        if let Some(content) = local {
+            let complete = content.content.is_some();
+            self.show(content);
+            if complete {
+                return;
+            }
+        }
+        let client = ApiClient::new(&self.state);
+        ctx.link().send_future(async move {
+            let result = client.get_content_item(&id).await.map(Box::new);
+            ArticleMessage::Fetched(id, result)
+        });
+    }
+
+    fn load_links(&self, ctx: &Context<Self>) {
+        let client = ApiClient::new(&self.state);
+        ctx.link().send_future(async move {
+            match client.get_links().await {
+                Ok(data) => ArticleMessage::Links(data),
+                Err(_) => ArticleMessage::Links(vec![]),
+            }
+        });
+    }
+
+    /// Displays the item and marks it as read.
+    fn show(&mut self, content: ContentDto) {
+        let shown = self
+            .article
+            .as_ref()
+            .is_some_and(|el| el.content_id == content.content_id);
+        if !shown {
+            let ids = vec![content.content_id.clone()];
+            let local_ids = ids.clone();
+            self.read_dispatch.reduce(move |s| s.mark_read(&local_ids));
+            let client = ApiClient::new(&self.state);
+            spawn_local(async move {
+                if let Err(error) = client.mark_read(ids).await {
+                    if !error.is_unsupported() {
+                        error!("read state sync failed: {}", error);
+                    }
+                }
+            });
+        }
+        self.article = Some(content);
+    }
+
+    /// Way back to the stream and to the items around this one.
+    fn html_nav(&self, ctx: &Context<Self>) -> Html {
+        let stream = Route::recognize(&self.stream.path).unwrap_or(Route::Home);
+        let (previous, next) = self.stream.neighbours(&ctx.props().id);
+        let step = |content: Option<&ContentDto>, label: &str, icon: &str| match content {
+            Some(content) => html!(
+                <Link<Route> classes="reader-step" to={Route::Article { id: content.content_id.clone() }}>
+                    <span title={content.title.clone()}>{label}</span>
+                    <i class={icon.to_string()}></i>
+                </Link<Route>>
+            ),
+            None => html!(<span class="reader-step reader-step-disabled">{label}</span>),
+        };
+        html!(
+            <nav class="reader-nav">
+                <Link<Route, PageQuery> classes="reader-back" to={stream} query={Some(self.stream.page.clone())}>
+                    <i class="fas fa-angle-left"></i>{" back to the stream"}
+                </Link<Route, PageQuery>>
+                <div class="reader-steps">
+                    {step(previous, "previous", "fas fa-angle-up")}
+                    {step(next, "next", "fas fa-angle-down")}
+                </div>
+            </nav>
+        )
+    }
+
+    fn html_article(&self, ctx: &Context<Self>, content: &ContentDto) -> Html {
+        let feed = self
+            .links
+            .iter()
+            .find(|link| link.link_id == content.link_id);
+        let starred = self.star_state.is_starred(&content.content_id);
+        let date = match parse_date(&content.date) {
+            Some(ms) => html!(
+                <time class="reader-date" datetime={content.date.clone()} title={absolute(ms)}>
+                    {format!("{} ({})", absolute(ms), relative(ms, Date::now()))}
+                </time>
+            ),
+            None => html!(<span class="reader-date">{content.date.clone()}</span>),
+        };
+        let body = content
+            .content
+            .clone()
+            .or_else(|| content.description.clone())
+            .unwrap_or_default();
+        html!(
+            <article class="reader-article">
+                <header class="reader-header">
+                    <h1 class="reader-title">{content.title.clone()}</h1>
+                    <div class="reader-meta">
+                        {
+                            match feed {
+                                Some(link) => html!(
+                                    <span class="reader-feed">
+                                        <FeedIcon url={link.icon()}/>
+                                        {link.display_name()}
+                                    </span>
+                                ),
+                                None => html!(),
+                            }
+                        }
+                        {
+                            match &content.author {
+                                Some(author) => html!(<span class="reader-author">{format!("by {}", author)}</span>),
+                                None => html!(),
+                            }
+                        }
+                        {date}
+                    </div>
+                    <div class="reader-actions">
+                        <a class="reader-original" target="_blank" rel="noopener noreferrer" href={content.link_url.clone()}>
+                            {"open original "}<i class="fa-solid fa-arrow-up-right-from-square"></i>
+                        </a>
+                        <button class={classes!("button-icon", "content-star", starred.then_some("content-starred"))}
+                            title={if starred { "unstar" } else { "star" }}
+                            onclick={ctx.link().callback(|_| ArticleMessage::ToggleStar)}>
+                            <i class={if starred { "fa-solid fa-star" } else { "fa-regular fa-star" }}></i>
+                        </button>
+                    </div>
+                </header>
+                <div class="reader-content"><SafeHtml html={body}/></div>
+            </article>
+        )
+    }
+}
@@ -0,0 +1,15 @@
+use yew::prelude::*;
+
+#[derive(PartialEq, Properties)]
+pub struct FeedIconProps {
+    pub url: Option<String>,
+}
+
+/// Favicon of a feed, or a generic feed icon when it has none.
+#[function_component(FeedIcon)]
+pub fn feed_icon(props: &FeedIconProps) -> Html {
+    match &props.url {
+        Some(url) => html!(<img class="feed-icon" src={url.clone()} alt="" loading="lazy"/>),
+        None => html!(<i class="fa-solid fa-rss feed-icon feed-icon-fallback"></i>),
+    }
+}
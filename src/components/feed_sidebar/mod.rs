@@ -0,0 +1,115 @@
+use crate::components::feed_icon::FeedIcon;
+use crate::dto::LinkDto;
+use crate::router::Route;
+use crate::store::{Folder, FolderStore};
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use yew::prelude::*;
+use yew_router::prelude::*;
+use yewdux::dispatch::{Dispatch, Dispatcher};
+use yewdux::prelude::BasicStore;
+
+#[derive(PartialEq, Properties)]
+pub struct FeedSidebarProps {
+    pub links: Vec<LinkDto>,
+    /// Unread items per `link_id`.
+    pub counts: BTreeMap<String, u32>,
+    pub folders: Rc<FolderStore>,
+    pub active: Option<String>,
+    pub active_folder: Option<String>,
+}
+
+/// List of the user's feeds, grouped in collapsible folders, linking to the
+/// per-feed and per-folder streams.
+#[function_component(FeedSidebar)]
+pub fn feed_sidebar(props: &FeedSidebarProps) -> Html {
+    let total: u32 = props.counts.values().sum();
+    let unfiled = props
+        .links
+        .iter()
+        .filter(|link| props.folders.folder_of(&link.link_id).is_none());
+    html!(
+        <aside class="feed-sidebar">
+            <ul class="feed-sidebar-list">
+                <li class={classes!("feed-sidebar-item", (props.active.is_none() && props.active_folder.is_none()).then_some("feed-sidebar-active"))}>
+                    <Link<Route> classes={"feed-sidebar-link"} to={Route::Home}>
+                        {"All feeds"}{badge(total)}
+                    </Link<Route>>
+                </li>
+                { props.folders.folders.iter().map(|folder| folder_group(props, folder)).collect::<Html>() }
+                { unfiled.map(|link| feed_item(props, link)).collect::<Html>() }
+            </ul>
+        </aside>
+    )
+}
+
+fn folder_group(props: &FeedSidebarProps, folder: &Folder) -> Html {
+    let links: Vec<&LinkDto> = props
+        .links
+        .iter()
+        .filter(|link| props.folders.folder_of(&link.link_id) == Some(folder.folder_id.as_str()))
+        .collect();
+    let count: u32 = links
+        .iter()
+        .filter_map(|link| props.counts.get(&link.link_id))
+        .sum();
+    let collapsed = props.folders.is_collapsed(&folder.folder_id);
+    let active = props.active_folder.as_deref() == Some(folder.folder_id.as_str());
+    let folder_id = folder.folder_id.clone();
+    let toggle = Callback::from(move |_| {
+        let folder_id = folder_id.clone();
+        Dispatch::<BasicStore<FolderStore>>::new().reduce(move |s| s.toggle_collapsed(&folder_id));
+    });
+    html!(
+        <li class="feed-sidebar-folder">
+            <div class={classes!("feed-sidebar-item", active.then_some("feed-sidebar-active"))}>
+                <button class="button-icon feed-sidebar-toggle" onclick={toggle}
+                    title={if collapsed { "expand" } else { "collapse" }}>
+                    <i class={if collapsed { "fa-solid fa-caret-right" } else { "fa-solid fa-caret-down" }}></i>
+                </button>
+                <Link<Route> classes={"feed-sidebar-link"} to={Route::Folder { id: folder.folder_id.clone() }}>
+                    <i class="fa-regular fa-folder feed-icon"></i>{folder.name.clone()}{badge(count)}
+                </Link<Route>>
+            </div>
+            {
+                if collapsed {
+                    html!()
+                } else {
+                    html!(
+                        <ul class="feed-sidebar-list feed-sidebar-nested">
+                            { links.into_iter().map(|link| feed_item(props, link)).collect::<Html>() }
+                        </ul>
+                    )
+                }
+            }
+        </li>
+    )
+}
+
+fn feed_item(props: &FeedSidebarProps, link: &LinkDto) -> Html {
+    let active = props.active.as_deref() == Some(link.link_id.as_str());
+    let count = props.counts.get(&link.link_id).copied().unwrap_or(0);
+    html!(
+        <li class={classes!("feed-sidebar-item", active.then_some("feed-sidebar-active"))}>
+            <Link<Route> classes={"feed-sidebar-link"} to={Route::Feed { id: link.link_id.clone() }}>
+                <FeedIcon url={link.icon()}/>{link.display_name()}
+                {
+                    if link.paused {
+                        html!(<i class="fa-solid fa-pause feed-sidebar-paused" title="paused"></i>)
+                    } else {
+                        html!()
+                    }
+                }
+                {badge(count)}
+            </Link<Route>>
+        </li>
+    )
+}
+
+fn badge(count: u32) -> Html {
+    if count == 0 {
+        html!()
+    } else {
+        html!(<span class="content-feed-count-badge">{count}</span>)
+    }
+}
@@ -0,0 +1,331 @@
+use super::{LinkComponent, LinkMessage};
+use crate::api::{ApiClient, ApiError};
+use crate::components::opml::download::download;
+use crate::dto::{LinkDto, LinkUpdateDto};
+use crate::opml::export_opml;
+use js_sys::{Function, Promise, Reflect};
+use log::error;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{window, EventTarget, HtmlSelectElement};
+use yew::prelude::*;
+use yewdux::dispatch::Dispatcher;
+
+/// Value of the folder select that moves links out of their folder.
+const UNFILED: &str = "unfiled";
+
+pub enum BulkAction {
+    Delete,
+    /// Moves the links to the folder, or out of their folder with `None`.
+    Move(Option<String>),
+    Pause(bool),
+    Export,
+    CopyUrls,
+}
+
+/// Outcome of a bulk action, link by link.
+#[derive(Default)]
+pub struct BulkReport {
+    pub action: String,
+    pub total: usize,
+    pub succeeded: usize,
+    /// Names of the links that failed and why.
+    pub failed: Vec<(String, String)>,
+}
+
+impl BulkReport {
+    fn new(action: &str, total: usize) -> Self {
+        Self {
+            action: action.to_string(),
+            total,
+            ..Default::default()
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.succeeded + self.failed.len() >= self.total
+    }
+}
+
+impl LinkComponent {
+    /// Selected links, in list order.
+    fn selected_links(&self) -> Vec<LinkDto> {
+        self.links
+            .iter()
+            .filter(|link| self.selected.contains(&link.link_id))
+            .cloned()
+            .collect()
+    }
+
+    pub(super) fn bulk(&mut self, ctx: &Context<Self>, action: BulkAction) -> bool {
+        let links = self.selected_links();
+        if links.is_empty() {
+            return false;
+        }
+        match action {
+            BulkAction::Delete => {
+                let question = format!("Delete {} subscriptions?", links.len());
+                let confirmed = window()
+                    .and_then(|window| window.confirm_with_message(&question).ok())
+                    .unwrap_or(false);
+                if !confirmed {
+                    return false;
+                }
+                self.report = Some(BulkReport::new("Deleting", links.len()));
+                let client = ApiClient::new(&self.state);
+                let scope = ctx.link().clone();
+                spawn_local(async move {
+                    let mut unauthorized = false;
+                    for link in links {
+                        // After a 401 the rest fails the same way; report it without trying.
+                        let result = if unauthorized {
+                            Err(ApiError::Unauthorized)
+                        } else {
+                            client.remove_link(link.link_id.clone()).await
+                        };
+                        unauthorized = matches!(result, Err(ApiError::Unauthorized));
+                        scope.send_message(LinkMessage::BulkDeleted(link, result));
+                    }
+                });
+            }
+            BulkAction::Move(folder_id) => {
+                let ids: Vec<String> = links.iter().map(|link| link.link_id.clone()).collect();
+                self.folder_dispatch.reduce(move |s| {
+                    for link_id in &ids {
+                        s.move_link(link_id, folder_id.as_deref());
+                    }
+                });
+                // Folders are local, there is no outcome per link to report.
+                self.report = None;
+            }
+            BulkAction::Pause(paused) => {
+                let update = LinkUpdateDto {
+                    paused: Some(paused),
+                    ..Default::default()
+                };
+                let updated: Vec<LinkDto> = links.iter().map(|link| update.apply(link)).collect();
+                self.link_dispatch.reduce(move |s| {
+                    for link in updated {
+                        s.replace(link);
+                    }
+                });
+                let action = if paused { "Pausing" } else { "Resuming" };
+                self.report = Some(BulkReport::new(action, links.len()));
+                let client = ApiClient::new(&self.state);
+                let scope = ctx.link().clone();
+                spawn_local(async move {
+                    let mut unauthorized = false;
+                    for link in links {
+                        // After a 401 the rest fails the same way; roll it back without trying.
+                        let result = if unauthorized {
+                            Err(ApiError::Unauthorized)
+                        } else {
+                            client.update_link(link.link_id.clone(), &update).await
+                        };
+                        unauthorized = matches!(result, Err(ApiError::Unauthorized));
+                        scope.send_message(LinkMessage::BulkUpdated(link, result));
+                    }
+                });
+            }
+            BulkAction::Export => {
+                let opml = export_opml("RSS reader subscriptions", &links, &self.folders);
+                let mut report = BulkReport::new("Exporting", links.len());
+                match download("subscriptions.opml", "text/x-opml", &opml) {
+                    Ok(_) => report.succeeded = links.len(),
+                    Err(error) => {
+                        error!("export failed: {:?}", error);
+                        report.failed.push((
+                            "selection".to_string(),
+                            "could not export the subscriptions".to_string(),
+                        ));
+                        report.total = 1;
+                    }
+                }
+                self.report = Some(report);
+            }
+            BulkAction::CopyUrls => {
+                let text = links
+                    .iter()
+                    .map(|link| link.link.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                self.report = Some(BulkReport::new("Copying", links.len()));
+                ctx.link()
+                    .send_future(async move { LinkMessage::BulkCopied(copy_text(&text).await) });
+            }
+        }
+        true
+    }
+
+    pub(super) fn bulk_deleted(&mut self, link: LinkDto, result: Result<bool, ApiError>) {
+        let error = match result {
+            Ok(true) => {
+                let link_id = link.link_id;
+                self.selected.remove(&link_id);
+                let folder_id = link_id.clone();
+                self.folder_dispatch
+                    .reduce(move |s| s.move_link(&folder_id, None));
+                self.link_dispatch
+                    .reduce(move |s| s.links.retain(|link| link.link_id != link_id));
+                if let Some(report) = &mut self.report {
+                    report.succeeded += 1;
+                }
+                return;
+            }
+            Ok(false) => "the server refused to delete it".to_string(),
+            Err(error) => error.to_string(),
+        };
+        if let Some(report) = &mut self.report {
+            report.failed.push((link.display_name(), error));
+        }
+    }
+
+    /// Applies the answer of the backend, or rolls the link back to `previous`.
+    pub(super) fn bulk_updated(&mut self, previous: LinkDto, result: Result<LinkDto, ApiError>) {
+        let report = self.report.as_mut();
+        match result {
+            Ok(link) => {
+                if let Some(report) = report {
+                    report.succeeded += 1;
+                }
+                self.link_dispatch.reduce(move |s| s.replace(link));
+            }
+            Err(error) => {
+                if let Some(report) = report {
+                    report
+                        .failed
+                        .push((previous.display_name(), error.to_string()));
+                }
+                self.link_dispatch.reduce(move |s| s.replace(previous));
+            }
+        }
+    }
+
+    pub(super) fn bulk_copied(&mut self, result: Result<(), String>) {
+        if let Some(report) = &mut self.report {
+            match result {
+                Ok(_) => report.succeeded = report.total,
+                Err(error) => {
+                    report.total = 1;
+                    report.failed.push(("selection".to_string(), error));
+                }
+            }
+        }
+    }
+
+    pub(super) fn html_bulk_toolbar(&self, ctx: &Context<Self>) -> Html {
+        if self.links.is_empty() {
+            return html!();
+        }
+        let all = self
+            .links
+            .iter()
+            .all(|link| self.selected.contains(&link.link_id));
+        let none = self.selected.is_empty();
+        let move_to = ctx.link().batch_callback(|e: Event| {
+            let target: Option<EventTarget> = e.target();
+            let select = target.and_then(|t| t.dyn_into::<HtmlSelectElement>().ok())?;
+            let value = select.value();
+            select.set_value("");
+            match value.as_str() {
+                "" => None,
+                UNFILED => Some(LinkMessage::Bulk(BulkAction::Move(None))),
+                _ => Some(LinkMessage::Bulk(BulkAction::Move(Some(value)))),
+            }
+        });
+        html!(
+            <div class="link-bulk">
+                <div class="link-bulk-actions row-direction">
+                    <label class="link-bulk-item">
+                        <input class="primary-checkbox" type="checkbox" checked={all}
+                            onchange={ctx.link().callback(move |_| LinkMessage::SelectAll(!all))}/>
+                        {format!(" {} selected", self.selected.len())}
+                    </label>
+                    <button class="content-toolbar-button" disabled={none}
+                        onclick={ctx.link().callback(|_| LinkMessage::Bulk(BulkAction::Delete))}>{"delete"}</button>
+                    <select class="link-bulk-select" disabled={none} onchange={move_to}>
+                        <option value="" selected=true>{"move to folder"}</option>
+                        {
+                            self.folders.folders.iter().map(|folder| html!(
+                                <option value={folder.folder_id.clone()}>{folder.name.clone()}</option>
+                            )).collect::<Html>()
+                        }
+                        <option value={UNFILED}>{"unfiled"}</option>
+                    </select>
+                    <button class="content-toolbar-button" disabled={none}
+                        onclick={ctx.link().callback(|_| LinkMessage::Bulk(BulkAction::Pause(true)))}>{"pause"}</button>
+                    <button class="content-toolbar-button" disabled={none}
+                        onclick={ctx.link().callback(|_| LinkMessage::Bulk(BulkAction::Pause(false)))}>{"resume"}</button>
+                    <button class="content-toolbar-button" disabled={none}
+                        onclick={ctx.link().callback(|_| LinkMessage::Bulk(BulkAction::Export))}>{"export OPML"}</button>
+                    <button class="content-toolbar-button" disabled={none}
+                        onclick={ctx.link().callback(|_| LinkMessage::Bulk(BulkAction::CopyUrls))}>{"copy urls"}</button>
+                </div>
+                {self.html_bulk_report(ctx)}
+            </div>
+        )
+    }
+
+    fn html_bulk_report(&self, ctx: &Context<Self>) -> Html {
+        let report = match &self.report {
+            Some(report) => report,
+            None => return html!(),
+        };
+        let done = report.succeeded + report.failed.len();
+        html!(
+            <div class="link-bulk-report">
+                <p>
+                    {
+                        if report.finished() {
+                            format!("{} finished: {} succeeded, {} failed.", report.action, report.succeeded, report.failed.len())
+                        } else {
+                            format!("{} {} of {} ...", report.action, done, report.total)
+                        }
+                    }
+                    {
+                        if report.finished() {
+                            html!(
+                                <button class="button-icon" title="dismiss"
+                                    onclick={ctx.link().callback(|_| LinkMessage::DismissReport)}>
+                                    <i class="fa-solid fa-xmark"></i>
+                                </button>
+                            )
+                        } else {
+                            html!()
+                        }
+                    }
+                </p>
+                <ul class="opml-failures">
+                    {
+                        report.failed.iter().map(|(name, reason)| html!(
+                            <li class="error-message">{format!("{}: {}", name, reason)}</li>
+                        )).collect::<Html>()
+                    }
+                </ul>
+            </div>
+        )
+    }
+}
+
+/// Writes `text` to the clipboard through `navigator.clipboard.writeText`.
+async fn copy_text(text: &str) -> Result<(), String> {
+    let unavailable = || "the clipboard is not available".to_string();
+    let navigator = window().ok_or_else(unavailable)?.navigator();
+    let clipboard = Reflect::get(&navigator, &JsValue::from_str("clipboard"))
+        .ok()
+        .filter(|clipboard| !clipboard.is_undefined())
+        .ok_or_else(unavailable)?;
+    let write: Function = Reflect::get(&clipboard, &JsValue::from_str("writeText"))
+        .ok()
+        .and_then(|write| write.dyn_into().ok())
+        .ok_or_else(unavailable)?;
+    let promise: Promise = write
+        .call1(&clipboard, &JsValue::from_str(text))
+        .ok()
+        .and_then(|promise| promise.dyn_into().ok())
+        .ok_or_else(unavailable)?;
+    JsFuture::from(promise)
+        .await
+        .map(|_| ())
+        .map_err(|_| "the browser did not allow copying".to_string())
+}
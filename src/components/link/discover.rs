@@ -0,0 +1,266 @@
+use crate::dto::{DiscoveredFeedDto, FeedPreviewDto};
+use crate::feed::{parse_feed, resolve_url, FeedFormat};
+use crate::sanitize::decode_entities;
+use reqwasm::http::Request;
+
+/// Mime types of `<link rel="alternate">` elements pointing at feeds.
+const FEED_TYPES: [&str; 4] = [
+    "application/rss+xml",
+    "application/atom+xml",
+    "application/feed+json",
+    "application/rdf+xml",
+];
+
+/// Paths where sites commonly serve their feed.
+const COMMON_PATHS: [&str; 6] = [
+    "/feed",
+    "/rss",
+    "/rss.xml",
+    "/atom.xml",
+    "/feed.xml",
+    "/index.xml",
+];
+
+/// True for urls without a path, which are rarely feeds.
+pub fn is_homepage(url: &str) -> bool {
+    let rest = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    match rest.find(['/', '?', '#']) {
+        Some(index) => rest[index..].trim_end_matches('/').is_empty(),
+        None => true,
+    }
+}
+
+/// Looks for feeds from the browser, for backends that can not discover them.
+///
+/// Reading the page only works when the site allows cross-origin requests.
+/// When it advertises no feeds, the common feed paths are tried and the ones
+/// that parse as feeds are offered.
+pub async fn discover_locally(url: &str) -> Vec<DiscoveredFeedDto> {
+    let feeds = match fetch(url).await {
+        Some((content_type, html)) if content_type.contains("html") => find_feed_links(&html, url),
+        Some((_, body)) => as_feed(url, &body).into_iter().collect(),
+        None => vec![],
+    };
+    if !feeds.is_empty() {
+        return feeds;
+    }
+    let mut found = vec![];
+    for path in COMMON_PATHS {
+        let guess = resolve_url(url, path);
+        if let Some((_, body)) = fetch(&guess).await {
+            found.extend(as_feed(&guess, &body));
+        }
+    }
+    found
+}
+
+/// The document at `url`, when it is a feed.
+fn as_feed(url: &str, body: &str) -> Option<DiscoveredFeedDto> {
+    let feed = parse_feed(body).ok()?;
+    Some(DiscoveredFeedDto {
+        url: url.to_string(),
+        title: Some(feed.title).filter(|title| !title.is_empty()),
+        kind: Some(mime_type(feed.format).to_string()),
+    })
+}
+
+/// Previews a feed from the browser, for backends that can not preview feeds.
+pub async fn preview_locally(url: &str) -> Option<FeedPreviewDto> {
+    let (_, body) = fetch(url).await?;
+    parse_feed(&body).ok().map(|feed| feed.to_preview())
+}
+
+/// Content type and body of the page, when the site allows reading it.
+async fn fetch(url: &str) -> Option<(String, String)> {
+    let res = Request::get(url).send().await.ok()?;
+    if !res.ok() {
+        return None;
+    }
+    let content_type = res
+        .headers()
+        .get("content-type")
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    let body = res.text().await.ok()?;
+    Some((content_type, body))
+}
+
+fn mime_type(format: FeedFormat) -> &'static str {
+    match format {
+        FeedFormat::Rss2 => "application/rss+xml",
+        FeedFormat::Rss1 => "application/rdf+xml",
+        FeedFormat::Atom => "application/atom+xml",
+        FeedFormat::JsonFeed => "application/feed+json",
+    }
+}
+
+/// Feeds advertised with `<link rel="alternate">` in the head of a page.
+pub fn find_feed_links(html: &str, base_url: &str) -> Vec<DiscoveredFeedDto> {
+    let lower = html.to_ascii_lowercase();
+    let mut feeds: Vec<DiscoveredFeedDto> = vec![];
+    let mut pos = 0;
+    while let Some(index) = lower[pos..].find("<link") {
+        let start = pos + index + 5;
+        let end = lower[start..]
+            .find('>')
+            .map(|index| start + index)
+            .unwrap_or(lower.len());
+        pos = end;
+        if !lower[start..].starts_with(|c: char| c.is_whitespace()) {
+            continue;
+        }
+        let attributes = parse_attributes(&html[start..end]);
+        let attr = |name: &str| {
+            attributes
+                .iter()
+                .find(|(attr, _)| attr == name)
+                .map(|(_, value)| value.trim())
+        };
+        let alternate = attr("rel").is_some_and(|rel| {
+            rel.split_whitespace()
+                .any(|rel| rel.eq_ignore_ascii_case("alternate"))
+        });
+        let kind = attr("type").unwrap_or("").to_ascii_lowercase();
+        let href = attr("href").unwrap_or("");
+        if !alternate || href.is_empty() || !is_feed_type(&kind, href) {
+            continue;
+        }
+        let url = resolve_url(base_url, href);
+        if feeds.iter().any(|feed| feed.url == url) {
+            continue;
+        }
+        feeds.push(DiscoveredFeedDto {
+            url,
+            title: attr("title")
+                .filter(|title| !title.is_empty())
+                .map(str::to_string),
+            kind: Some(kind),
+        });
+    }
+    feeds
+}
+
+/// Plain `application/json` is used for all kinds of data, it only counts
+/// for JSON Feed's conventional `feed.json`.
+fn is_feed_type(kind: &str, href: &str) -> bool {
+    if kind == "application/json" {
+        let path = href.split(['?', '#']).next().unwrap_or(href);
+        return path.ends_with("feed.json");
+    }
+    FEED_TYPES.contains(&kind)
+}
+
+/// Attributes of a tag as lowercase names and decoded values.
+fn parse_attributes(tag: &str) -> Vec<(String, String)> {
+    let mut attributes = vec![];
+    let mut rest = tag;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+        if rest.is_empty() {
+            return attributes;
+        }
+        let len = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '/')
+            .unwrap_or(rest.len());
+        let name = rest[..len].to_ascii_lowercase();
+        rest = rest[len..].trim_start();
+        let mut value = String::new();
+        if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            let (raw, remaining) = match after.chars().next() {
+                Some(quote) if quote == '"' || quote == '\'' => {
+                    let body = &after[1..];
+                    let len = body.find(quote).unwrap_or(body.len());
+                    (&body[..len], &body[(len + 1).min(body.len())..])
+                }
+                _ => {
+                    let len = after.find(char::is_whitespace).unwrap_or(after.len());
+                    after.split_at(len)
+                }
+            };
+            value = decode_entities(raw);
+            rest = remaining;
+        }
+        if !name.is_empty() {
+            attributes.push((name, value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!doctype html><html><head>
+        <link rel="stylesheet" href="/style.css">
+        <LINK REL="alternate" TYPE="application/rss+xml" TITLE="Posts &amp; news" HREF="/feed.xml">
+        <link rel='alternate' type='application/atom+xml' href='atom.xml' />
+        <link rel="alternate" type="application/rss+xml" href="https://blog.example.com/feed.xml">
+        <link rel="alternate" type="application/json" href="/api/posts.json">
+        <link rel="alternate" type="application/json" href="/feed.json?v=2">
+        <link rel="alternate" type="text/html" hreflang="de" href="/de/">
+        <link rel="alternate" type="application/rss+xml" href="">
+        <link rel="preload" type="application/rss+xml" href="/other.xml">
+        <linkage rel="alternate" type="application/rss+xml" href="/nope.xml">
+    </head><body></body></html>"#;
+
+    #[test]
+    fn finds_advertised_feeds() {
+        let feeds = find_feed_links(PAGE, "https://blog.example.com/posts/");
+        let found: Vec<(&str, Option<&str>, Option<&str>)> = feeds
+            .iter()
+            .map(|feed| {
+                (
+                    feed.url.as_str(),
+                    feed.title.as_deref(),
+                    feed.kind.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "https://blog.example.com/feed.xml",
+                    Some("Posts & news"),
+                    Some("application/rss+xml")
+                ),
+                (
+                    "https://blog.example.com/posts/atom.xml",
+                    None,
+                    Some("application/atom+xml")
+                ),
+                (
+                    "https://blog.example.com/feed.json?v=2",
+                    None,
+                    Some("application/json")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn json_only_counts_for_feed_json() {
+        assert!(is_feed_type("application/feed+json", "/anything"));
+        assert!(is_feed_type("application/json", "/feed.json"));
+        assert!(is_feed_type(
+            "application/json",
+            "https://a.example/feed.json#x"
+        ));
+        assert!(!is_feed_type("application/json", "/api/posts.json"));
+        assert!(!is_feed_type("application/json", "/feed.json/comments"));
+        assert!(!is_feed_type("text/html", "/feed.xml"));
+        assert!(is_feed_type("application/rdf+xml", "/index.rdf"));
+    }
+
+    #[test]
+    fn homepages() {
+        assert!(is_homepage("https://example.com"));
+        assert!(is_homepage("https://example.com/"));
+        assert!(is_homepage("example.com"));
+        assert!(!is_homepage("https://example.com/?feed=rss2"));
+        assert!(!is_homepage("https://example.com/feed"));
+        assert!(!is_homepage("https://example.com/blog/"));
+    }
+}
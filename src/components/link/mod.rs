@@ -1,30 +1,108 @@
-use crate::api::{create_link, get_links};
+use crate::api::{ApiClient, ApiError};
 use crate::components;
-use crate::dto::{LinkCreatedDto, LinkDto};
-use crate::store::UserStore;
-use components::link_button::{Link, LinkData};
+use crate::dto::{DiscoveredFeedDto, FeedPreviewDto, LinkCreatedDto, LinkDto};
+use crate::store::{Folder, FolderStore, LinkStore, UserStore};
+use bulk::{BulkAction, BulkReport};
+use components::link_button::Link;
+use discover::{discover_locally, is_homepage, preview_locally};
+use gloo_timers::callback::Timeout;
 use log::error;
+use std::collections::BTreeSet;
 use std::rc::Rc;
+use validate::{normalize_url, validate_feed_url};
 use wasm_bindgen::JsCast;
 use web_sys::{EventTarget, HtmlInputElement};
+use yew::events::DragEvent;
 use yew::prelude::*;
-use yewdux::dispatch::Dispatch;
+use yewdux::dispatch::{Dispatch, Dispatcher};
 use yewdux::prelude::BasicStore;
 
 pub enum LinkMessage {
     UserState(Rc<UserStore>),
+    LinkState(Rc<LinkStore>),
     Success(Vec<LinkDto>),
     InputLink(String),
     Add,
+    PreviewLoaded(String, FeedPreviewDto),
+    PreviewFailed(String, ApiError),
+    Discovered(String, Vec<DiscoveredFeedDto>),
+    ToggleCandidate(String),
+    /// The user accepts subscribing to a feed that could not be previewed.
+    ConfirmUnavailable,
+    Subscribe,
+    CancelPreview,
     SuccessAdded(LinkCreatedDto),
-    Error(String),
+    Error(ApiError),
+    FolderState(Rc<FolderStore>),
+    InputFolder(String),
+    CreateFolder,
+    StartRename(String),
+    InputRename(String),
+    SaveRename,
+    CancelRename,
+    DeleteFolder(String),
+    /// A link was dropped on a folder, or on the unfiled list with `None`.
+    MoveLink(String, Option<String>),
+    Remove(String),
+    UndoRemove(String),
+    /// The undo delay of a removal ran out.
+    CommitRemove(String),
+    Removed(String, Result<bool, ApiError>),
+    DismissRemoveError(usize),
+    ToggleSelect(String),
+    SelectAll(bool),
+    Bulk(BulkAction),
+    BulkDeleted(LinkDto, Result<bool, ApiError>),
+    /// Answer to a bulk update of the link, carrying it as it was before.
+    BulkUpdated(LinkDto, Result<LinkDto, ApiError>),
+    BulkCopied(Result<(), String>),
+    DismissReport,
+}
+
+/// Time the user has to undo the removal of a link.
+const UNDO_MS: u32 = 5000;
+
+/// Link removed from the list but not yet deleted on the backend.
+pub struct PendingRemoval {
+    link: LinkDto,
+    /// Position in the list to restore the link to.
+    index: usize,
+    /// Runs out when the link should be deleted; `None` once the delete was sent.
+    timeout: Option<Timeout>,
+}
+
+/// Feed shown to the user before subscribing to it.
+pub enum Preview {
+    None,
+    Loading(String),
+    Ready(String, FeedPreviewDto),
+    /// The feed could not be previewed, with the reason; the url can still be
+    /// subscribed to once the user confirmed it.
+    Unavailable(String, String, bool),
+    /// Looking for feeds on a web page.
+    Discovering(String),
+    /// Feeds found on a web page and the urls picked to subscribe to.
+    Discovered(String, Vec<DiscoveredFeedDto>, BTreeSet<String>),
 }
 
 pub struct LinkComponent {
     _dispatch: Dispatch<BasicStore<UserStore>>,
+    link_dispatch: Dispatch<BasicStore<LinkStore>>,
+    folder_dispatch: Dispatch<BasicStore<FolderStore>>,
     state: Rc<UserStore>,
+    folders: Rc<FolderStore>,
     links: Vec<LinkDto>,
     link: String,
+    error: Option<String>,
+    preview: Preview,
+    folder_name: String,
+    /// Folder being renamed and its new name.
+    renaming: Option<(String, String)>,
+    removals: Vec<PendingRemoval>,
+    remove_errors: Vec<String>,
+    /// `link_id`s selected for a bulk action.
+    selected: BTreeSet<String>,
+    report: Option<BulkReport>,
 }
 impl Component for LinkComponent {
     type Message = LinkMessage;
@@ -32,11 +110,24 @@ impl Component for LinkComponent {
 
     fn create(ctx: &Context<Self>) -> Self {
         let dispatch = Dispatch::bridge_state(ctx.link().callback(LinkMessage::UserState));
+        let link_dispatch = Dispatch::bridge_state(ctx.link().callback(LinkMessage::LinkState));
+        let folder_dispatch = Dispatch::bridge_state(ctx.link().callback(LinkMessage::FolderState));
         Self {
             _dispatch: dispatch,
+            link_dispatch,
+            folder_dispatch,
             state: Default::default(),
+            folders: Default::default(),
             links: vec![],
             link: "".to_string(),
+            error: None,
+            preview: Preview::None,
+            folder_name: "".to_string(),
+            renaming: None,
+            removals: vec![],
+            remove_errors: vec![],
+            selected: BTreeSet::new(),
+            report: None,
         }
     }
 
@@ -47,46 +138,332 @@ impl Component for LinkComponent {
                 if self.state.token.is_empty() || self.state.token.eq("error") {
                     return true;
                 }
-                let token = self.state.token.clone();
-                ctx.link().send_future(async {
-                    match get_links(token).await {
+                let client = ApiClient::new(&self.state);
+                ctx.link().send_future(async move {
+                    match client.get_links().await {
                         Ok(data) => LinkMessage::Success(data),
-                        Err(_) => LinkMessage::Success(vec![]),
+                        Err(error) => LinkMessage::Error(error),
                     }
                 });
                 true
             }
-            LinkMessage::Success(data) => {
-                self.links = data;
+            LinkMessage::LinkState(state) => {
+                self.links = state.links.clone();
+                let links = &self.links;
+                self.selected
+                    .retain(|link_id| links.iter().any(|link| &link.link_id == link_id));
                 true
             }
+            LinkMessage::Success(mut data) => {
+                data.retain(|link| {
+                    !self
+                        .removals
+                        .iter()
+                        .any(|removal| removal.link.link_id == link.link_id)
+                });
+                self.link_dispatch.reduce(move |s| s.links = data);
+                false
+            }
             LinkMessage::InputLink(data) => {
                 self.link = data;
+                self.error = None;
                 true
             }
             LinkMessage::Add => {
-                let link = self.link.clone();
-                let token = self.state.token.clone();
-                ctx.link().send_future(async {
-                    match create_link(token, link).await {
-                        Ok(data) => LinkMessage::SuccessAdded(data),
-                        Err(_) => LinkMessage::Error("error".to_string()),
+                let url = match validate_feed_url(&self.link, &self.links) {
+                    Ok(url) => url,
+                    Err(error) => {
+                        self.error = Some(error);
+                        return true;
+                    }
+                };
+                self.error = None;
+                if is_homepage(&url) {
+                    self.discover(ctx, url);
+                    return true;
+                }
+                self.preview = Preview::Loading(url.clone());
+                let client = ApiClient::new(&self.state);
+                ctx.link().send_future(async move {
+                    match client.preview_feed(url.clone()).await {
+                        Ok(data) => LinkMessage::PreviewLoaded(url, data),
+                        Err(error) if error.is_unsupported() => match preview_locally(&url).await {
+                            Some(data) => LinkMessage::PreviewLoaded(url, data),
+                            None => LinkMessage::PreviewFailed(url, error),
+                        },
+                        Err(error) => LinkMessage::PreviewFailed(url, error),
                     }
                 });
                 true
             }
+            LinkMessage::PreviewLoaded(url, data) => {
+                self.preview = Preview::Ready(url, data);
+                true
+            }
+            LinkMessage::PreviewFailed(url, error) => {
+                if error.is_unsupported() {
+                    let reason = format!(
+                        "The backend can not preview feeds ({}) and the site does not let the browser read it.",
+                        error
+                    );
+                    self.preview = Preview::Unavailable(url, reason, false);
+                    return true;
+                }
+                if let ApiError::Validation(_) | ApiError::Server { status: 422, .. } = error {
+                    // Probably a web page; look for the feeds it links to.
+                    self.discover(ctx, url);
+                    return true;
+                }
+                self.preview = Preview::None;
+                self.error = Some(error.to_string());
+                true
+            }
+            LinkMessage::Discovered(url, mut feeds) => {
+                let subscribed: Vec<String> = self
+                    .links
+                    .iter()
+                    .map(|link| normalize_url(&link.link))
+                    .collect();
+                feeds.retain(|feed| !subscribed.contains(&normalize_url(&feed.url)));
+                if feeds.is_empty() {
+                    self.preview = Preview::None;
+                    self.error = Some(format!("No feeds were found on {}", url));
+                    return true;
+                }
+                // Preselect the first advertised feed; guessed paths may not exist.
+                let selected = feeds
+                    .iter()
+                    .find(|feed| feed.kind.is_some())
+                    .map(|feed| feed.url.clone())
+                    .into_iter()
+                    .collect();
+                self.preview = Preview::Discovered(url, feeds, selected);
+                true
+            }
+            LinkMessage::ToggleCandidate(url) => {
+                if let Preview::Discovered(_, _, selected) = &mut self.preview {
+                    if !selected.remove(&url) {
+                        selected.insert(url);
+                    }
+                }
+                true
+            }
+            LinkMessage::ConfirmUnavailable => {
+                if let Preview::Unavailable(_, _, confirmed) = &mut self.preview {
+                    *confirmed = !*confirmed;
+                }
+                true
+            }
+            LinkMessage::Subscribe => {
+                let urls: Vec<String> = match &self.preview {
+                    Preview::Ready(url, _) | Preview::Unavailable(url, _, true) => {
+                        vec![url.clone()]
+                    }
+                    Preview::Discovered(_, _, selected) => selected.iter().cloned().collect(),
+                    _ => return false,
+                };
+                for url in urls {
+                    let client = ApiClient::new(&self.state);
+                    ctx.link().send_future(async move {
+                        match client.create_link(url).await {
+                            Ok(data) => LinkMessage::SuccessAdded(data),
+                            Err(error) => LinkMessage::Error(error),
+                        }
+                    });
+                }
+                false
+            }
+            LinkMessage::CancelPreview => {
+                self.preview = Preview::None;
+                true
+            }
             LinkMessage::SuccessAdded(data) => {
-                self.links.push(LinkDto {
-                    link_id: data.link_id,
-                    link: data.link,
-                });
+                self.link_dispatch
+                    .reduce(move |s| s.links.push(data.into()));
                 self.link = "".to_string();
+                self.error = None;
+                self.preview = Preview::None;
                 true
             }
             LinkMessage::Error(data) => {
                 error!("error {}", data);
+                self.error = Some(match data {
+                    ApiError::Conflict(_) => "You are already subscribed to this feed".to_string(),
+                    data => data.to_string(),
+                });
+                true
+            }
+            LinkMessage::FolderState(state) => {
+                self.folders = state;
+                true
+            }
+            LinkMessage::InputFolder(name) => {
+                self.folder_name = name;
+                false
+            }
+            LinkMessage::CreateFolder => {
+                let name = std::mem::take(&mut self.folder_name);
+                if name.trim().is_empty() {
+                    return true;
+                }
+                self.folder_dispatch.reduce(move |s| {
+                    s.create(&name);
+                });
+                true
+            }
+            LinkMessage::StartRename(folder_id) => {
+                let name = self
+                    .folders
+                    .folder(&folder_id)
+                    .map(|folder| folder.name.clone())
+                    .unwrap_or_default();
+                self.renaming = Some((folder_id, name));
+                true
+            }
+            LinkMessage::InputRename(name) => {
+                if let Some((_, current)) = &mut self.renaming {
+                    *current = name;
+                }
+                false
+            }
+            LinkMessage::SaveRename => {
+                if let Some((folder_id, name)) = self.renaming.take() {
+                    if !name.trim().is_empty() {
+                        self.folder_dispatch
+                            .reduce(move |s| s.rename(&folder_id, &name));
+                    }
+                }
+                true
+            }
+            LinkMessage::CancelRename => {
+                self.renaming = None;
+                true
+            }
+            LinkMessage::DeleteFolder(folder_id) => {
+                self.folder_dispatch.reduce(move |s| s.delete(&folder_id));
+                false
+            }
+            LinkMessage::MoveLink(link_id, folder_id) => {
+                self.folder_dispatch
+                    .reduce(move |s| s.move_link(&link_id, folder_id.as_deref()));
                 false
             }
+            LinkMessage::Remove(link_id) => {
+                let index = match self.links.iter().position(|link| link.link_id == link_id) {
+                    Some(index) => index,
+                    None => return false,
+                };
+                let commit = {
+                    let link_id = link_id.clone();
+                    ctx.link()
+                        .callback(move |_| LinkMessage::CommitRemove(link_id.clone()))
+                };
+                self.removals.push(PendingRemoval {
+                    link: self.links[index].clone(),
+                    index,
+                    timeout: Some(Timeout::new(UNDO_MS, move || commit.emit(()))),
+                });
+                self.link_dispatch
+                    .reduce(move |s| s.links.retain(|link| link.link_id != link_id));
+                true
+            }
+            LinkMessage::UndoRemove(link_id) => {
+                if let Some(removal) = self.take_removal(&link_id) {
+                    self.restore(removal);
+                }
+                true
+            }
+            LinkMessage::CommitRemove(link_id) => {
+                let removal = self
+                    .removals
+                    .iter_mut()
+                    .find(|removal| removal.link.link_id == link_id);
+                if let Some(removal) = removal {
+                    removal.timeout = None;
+                    let client = ApiClient::new(&self.state);
+                    ctx.link().send_future(async move {
+                        let result = client.remove_link(link_id.clone()).await;
+                        LinkMessage::Removed(link_id, result)
+                    });
+                }
+                true
+            }
+            LinkMessage::Removed(link_id, result) => {
+                let removal = match self.take_removal(&link_id) {
+                    Some(removal) => removal,
+                    None => return false,
+                };
+                let error = match result {
+                    Ok(true) => {
+                        self.folder_dispatch
+                            .reduce(move |s| s.move_link(&link_id, None));
+                        return true;
+                    }
+                    Ok(false) => "the server refused to delete it".to_string(),
+                    Err(error) => error.to_string(),
+                };
+                self.remove_errors.push(format!(
+                    "Could not remove {}: {}",
+                    removal.link.display_name(),
+                    error
+                ));
+                self.restore(removal);
+                true
+            }
+            LinkMessage::DismissRemoveError(index) => {
+                if index < self.remove_errors.len() {
+                    self.remove_errors.remove(index);
+                }
+                true
+            }
+            LinkMessage::ToggleSelect(link_id) => {
+                if !self.selected.remove(&link_id) {
+                    self.selected.insert(link_id);
+                }
+                true
+            }
+            LinkMessage::SelectAll(all) => {
+                self.selected = if all {
+                    self.links.iter().map(|link| link.link_id.clone()).collect()
+                } else {
+                    BTreeSet::new()
+                };
+                true
+            }
+            LinkMessage::Bulk(action) => self.bulk(ctx, action),
+            LinkMessage::BulkDeleted(link, result) => {
+                self.bulk_deleted(link, result);
+                true
+            }
+            LinkMessage::BulkUpdated(link, result) => {
+                self.bulk_updated(link, result);
+                true
+            }
+            LinkMessage::BulkCopied(result) => {
+                self.bulk_copied(result);
+                true
+            }
+            LinkMessage::DismissReport => {
+                self.report = None;
+                true
+            }
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        // Leaving the page ends the undo delay early.
+        let client = ApiClient::new(&self.state);
+        for removal in self
+            .removals
+            .drain(..)
+            .filter(|removal| removal.timeout.is_some())
+        {
+            let client = client.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(error) = client.remove_link(removal.link.link_id).await {
+                    error!("failed to remove link: {}", error);
+                }
+            });
         }
     }
 
@@ -94,35 +471,224 @@ impl Component for LinkComponent {
         let change = |e: FocusEvent| e.prevent_default();
         html!(
             <div class="row-direction">
-                <div class="form-container form-link-container center">
+                <div class="form-container form-link-container center column-direction">
                     <form class="form form-link" onsubmit={change}>
                         {self.html_input_link(ctx)}
+                        {self.html_error()}
                         {self.html_button_login(ctx)}
                     </form>
+                    {self.html_preview(ctx)}
                 </div>
-                <ul class="list-link">{self.html_list()}</ul>
+                <div class="list-link">
+                    {self.html_new_folder(ctx)}
+                    {self.html_bulk_toolbar(ctx)}
+                    {self.html_folders(ctx)}
+                </div>
+                {self.html_toasts(ctx)}
             </div>
         )
     }
 }
 
 impl LinkComponent {
-    fn html_list(&self) -> Html {
-        self.links
+    /// Asks the backend for the feeds of the page at `url`, falling back to
+    /// looking for them from the browser.
+    fn discover(&mut self, ctx: &Context<Self>, url: String) {
+        self.preview = Preview::Discovering(url.clone());
+        let client = ApiClient::new(&self.state);
+        ctx.link().send_future(async move {
+            let feeds = match client.discover_feeds(url.clone()).await {
+                Ok(feeds) => feeds,
+                Err(error) => {
+                    if !error.is_unsupported() {
+                        error!("feed discovery failed: {}", error);
+                    }
+                    discover_locally(&url).await
+                }
+            };
+            LinkMessage::Discovered(url, feeds)
+        });
+    }
+
+    fn take_removal(&mut self, link_id: &str) -> Option<PendingRemoval> {
+        let index = self
+            .removals
             .iter()
+            .position(|removal| removal.link.link_id == link_id)?;
+        Some(self.removals.remove(index))
+    }
+
+    /// Puts a link whose removal was undone or failed back into the list.
+    fn restore(&self, removal: PendingRemoval) {
+        let PendingRemoval { link, index, .. } = removal;
+        self.link_dispatch.reduce(move |s| {
+            let index = index.min(s.links.len());
+            s.links.insert(index, link);
+        });
+    }
+
+    fn html_toasts(&self, ctx: &Context<Self>) -> Html {
+        if self.removals.is_empty() && self.remove_errors.is_empty() {
+            return html!();
+        }
+        html!(
+            <div class="toast-container">
+                {
+                    self.removals.iter().map(|removal| {
+                        let name = removal.link.display_name();
+                        if removal.timeout.is_none() {
+                            return html!(<div class="toast">{format!("Removing {} ...", name)}</div>);
+                        }
+                        let link_id = removal.link.link_id.clone();
+                        html!(
+                            <div class="toast">
+                                {format!("Removed {}", name)}
+                                <button class="toast-button"
+                                    onclick={ctx.link().callback(move |_| LinkMessage::UndoRemove(link_id.clone()))}>
+                                    {"undo"}
+                                </button>
+                            </div>
+                        )
+                    }).collect::<Html>()
+                }
+                {
+                    self.remove_errors.iter().enumerate().map(|(index, error)| html!(
+                        <div class="toast toast-error">
+                            {error}
+                            <button class="toast-button" title="dismiss"
+                                onclick={ctx.link().callback(move |_| LinkMessage::DismissRemoveError(index))}>
+                                <i class="fa-solid fa-xmark"></i>
+                            </button>
+                        </div>
+                    )).collect::<Html>()
+                }
+            </div>
+        )
+    }
+
+    fn html_list(&self, ctx: &Context<Self>, links: Vec<&LinkDto>) -> Html {
+        links
+            .into_iter()
             .map(|el| {
-                let link = LinkData {
-                    link_id: el.link_id.clone(),
-                    link: el.link.clone(),
-                    token: self.state.token.clone(),
-                };
+                let link_id = el.link_id.clone();
+                let onremove = ctx.link().callback(LinkMessage::Remove);
+                let onselect = ctx.link().callback(LinkMessage::ToggleSelect);
+                let selected = self.selected.contains(&el.link_id);
+                let ondragstart = Callback::from(move |e: DragEvent| {
+                    if let Some(data) = e.data_transfer() {
+                        let _ = data.set_data("text/plain", &link_id);
+                    }
+                });
                 html!(
-                    <Link link={link}/>
+                    <Link key={el.link_id.clone()} link={el.clone()} client={ApiClient::new(&self.state)} {ondragstart} {onremove} {onselect} {selected}/>
                 )
             })
             .collect::<Html>()
     }
 
+    fn html_new_folder(&self, ctx: &Context<Self>) -> Html {
+        let change = ctx.link().batch_callback(|e: Event| {
+            let target: Option<EventTarget> = e.target();
+            let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+            input.map(|input| LinkMessage::InputFolder(input.value()))
+        });
+        html!(
+            <div class="link-folder-new row-direction">
+                <input class="primary-input" type="text" placeholder="New folder"
+                    value={self.folder_name.clone()} onchange={change}/>
+                <button class="primary-button" onclick={ctx.link().callback(|_| LinkMessage::CreateFolder)}>
+                    {"create folder"}
+                </button>
+            </div>
+        )
+    }
+
+    fn html_folders(&self, ctx: &Context<Self>) -> Html {
+        let unfiled = self
+            .links
+            .iter()
+            .filter(|link| self.folders.folder_of(&link.link_id).is_none())
+            .collect();
+        html!(
+            <>
+                {
+                    self.folders.folders.iter().map(|folder| {
+                        let links = self
+                            .links
+                            .iter()
+                            .filter(|link| self.folders.folder_of(&link.link_id) == Some(folder.folder_id.as_str()))
+                            .collect();
+                        html!(
+                            <section class="link-folder" ondragover={allow_drop()}
+                                ondrop={self.drop_on(ctx, Some(folder.folder_id.clone()))}>
+                                {self.html_folder_header(ctx, folder)}
+                                <ul class="link-folder-list">{self.html_list(ctx, links)}</ul>
+                            </section>
+                        )
+                    }).collect::<Html>()
+                }
+                <section class="link-folder" ondragover={allow_drop()} ondrop={self.drop_on(ctx, None)}>
+                    {
+                        if self.folders.folders.is_empty() {
+                            html!()
+                        } else {
+                            html!(<h4 class="link-folder-header">{"Unfiled"}</h4>)
+                        }
+                    }
+                    <ul class="link-folder-list">{self.html_list(ctx, unfiled)}</ul>
+                </section>
+            </>
+        )
+    }
+
+    fn html_folder_header(&self, ctx: &Context<Self>, folder: &Folder) -> Html {
+        let folder_id = folder.folder_id.clone();
+        match &self.renaming {
+            Some((renaming_id, name)) if renaming_id == &folder.folder_id => {
+                let change = ctx.link().batch_callback(|e: Event| {
+                    let target: Option<EventTarget> = e.target();
+                    let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+                    input.map(|input| LinkMessage::InputRename(input.value()))
+                });
+                html!(
+                    <div class="link-folder-header row-direction">
+                        <input class="primary-input" type="text" value={name.clone()} onchange={change}/>
+                        <button class="button-icon" title="save" onclick={ctx.link().callback(|_| LinkMessage::SaveRename)}>
+                            <i class="fa-solid fa-check"></i>
+                        </button>
+                        <button class="button-icon" title="cancel" onclick={ctx.link().callback(|_| LinkMessage::CancelRename)}>
+                            <i class="fa-solid fa-xmark"></i>
+                        </button>
+                    </div>
+                )
+            }
+            _ => {
+                let rename_id = folder_id.clone();
+                html!(
+                    <div class="link-folder-header row-direction">
+                        <h4><i class="fa-regular fa-folder"></i>{format!(" {}", folder.name)}</h4>
+                        <button class="button-icon" title="rename"
+                            onclick={ctx.link().callback(move |_| LinkMessage::StartRename(rename_id.clone()))}>
+                            <i class="fa-solid fa-pen"></i>
+                        </button>
+                        <button class="button-icon" title="delete folder"
+                            onclick={ctx.link().callback(move |_| LinkMessage::DeleteFolder(folder_id.clone()))}>
+                            <i class="fa-regular fa-trash-can"></i>
+                        </button>
+                    </div>
+                )
+            }
+        }
+    }
+
+    fn drop_on(&self, ctx: &Context<Self>, folder_id: Option<String>) -> Callback<DragEvent> {
+        ctx.link().batch_callback(move |e: DragEvent| {
+            e.prevent_default();
+            let link_id = e.data_transfer()?.get_data("text/plain").ok()?;
+            Some(LinkMessage::MoveLink(link_id, folder_id.clone()))
+        })
+    }
+
     fn html_input_link(&self, ctx: &Context<Self>) -> Html {
         let change: Callback<Event> = ctx.link().batch_callback(|e: Event| {
             let target: Option<EventTarget> = e.target();
@@ -143,6 +709,119 @@ impl LinkComponent {
         }
     }
 
+    fn html_preview(&self, ctx: &Context<Self>) -> Html {
+        let actions = |disabled: bool| {
+            html!(
+                <div class="feed-preview-actions">
+                    <button class="primary-button" onclick={ctx.link().callback(|_| LinkMessage::Subscribe)} {disabled}>
+                        {"subscribe"}
+                    </button>
+                    <button class="primary-button" onclick={ctx.link().callback(|_| LinkMessage::CancelPreview)}>
+                        {"cancel"}
+                    </button>
+                </div>
+            )
+        };
+        match &self.preview {
+            Preview::None => html!(),
+            Preview::Loading(url) => html!(
+                <div class="feed-preview">{format!("Checking {} ...", url)}</div>
+            ),
+            Preview::Discovering(url) => html!(
+                <div class="feed-preview">{format!("Looking for feeds on {} ...", url)}</div>
+            ),
+            Preview::Discovered(url, feeds, selected) => html!(
+                <div class="feed-preview">
+                    <h4 class="feed-preview-title">{"Feeds found"}</h4>
+                    <p class="feed-preview-url">{url}</p>
+                    <ul class="feed-preview-items">
+                        {
+                            feeds.iter().map(|feed| {
+                                let toggle_url = feed.url.clone();
+                                html!(
+                                    <li class="feed-preview-item feed-candidate">
+                                        <label>
+                                            <input class="primary-checkbox" type="checkbox"
+                                                checked={selected.contains(&feed.url)}
+                                                onchange={ctx.link().callback(move |_| LinkMessage::ToggleCandidate(toggle_url.clone()))}/>
+                                            {format!(" {}", feed.title.clone().unwrap_or_else(|| feed.url.clone()))}
+                                        </label>
+                                        <span class="feed-preview-date">
+                                            {
+                                                match &feed.kind {
+                                                    Some(kind) => format!("{} · {}", feed.url, feed_kind(kind)),
+                                                    None => format!("{} · guessed, not verified", feed.url),
+                                                }
+                                            }
+                                        </span>
+                                    </li>
+                                )
+                            }).collect::<Html>()
+                        }
+                    </ul>
+                    {
+                        if selected.is_empty() {
+                            html!(<p class="feed-preview-hint">{"Pick at least one feed to subscribe to."}</p>)
+                        } else {
+                            html!()
+                        }
+                    }
+                    {actions(selected.is_empty())}
+                </div>
+            ),
+            Preview::Unavailable(url, reason, confirmed) => html!(
+                <div class="feed-preview">
+                    <p class="feed-preview-url">{url}</p>
+                    <p class="error-message">{reason}</p>
+                    <label class="feed-preview-confirm">
+                        <input class="primary-checkbox" type="checkbox" checked={*confirmed}
+                            onchange={ctx.link().callback(|_| LinkMessage::ConfirmUnavailable)}/>
+                        {" Subscribe anyway, it may not be a feed"}
+                    </label>
+                    {actions(!confirmed)}
+                </div>
+            ),
+            Preview::Ready(url, preview) => html!(
+                <div class="feed-preview">
+                    <h4 class="feed-preview-title">{&preview.title}</h4>
+                    <p class="feed-preview-url">{url}</p>
+                    {
+                        match &preview.description {
+                            Some(description) => html!(<p>{description}</p>),
+                            None => html!(),
+                        }
+                    }
+                    <p class="feed-preview-count">{format!("{} items", preview.item_count)}</p>
+                    <ul class="feed-preview-items">
+                        {
+                            preview.items.iter().take(5).map(|item| html!(
+                                <li class="feed-preview-item">
+                                    {&item.title}
+                                    {
+                                        match &item.date {
+                                            Some(date) => html!(<span class="feed-preview-date">{date}</span>),
+                                            None => html!(),
+                                        }
+                                    }
+                                </li>
+                            )).collect::<Html>()
+                        }
+                    </ul>
+                    {actions(false)}
+                </div>
+            ),
+        }
+    }
+
+    fn html_error(&self) -> Html {
+        match &self.error {
+            Some(error) => html!(
+                <div class="form-element column-direction center error-message">{error}</div>
+            ),
+            None => html!(),
+        }
+    }
+
     fn html_button_login(&self, ctx: &Context<Self>) -> Html {
         html!(
             <div class="form-element column-direction center">
@@ -153,3 +832,22 @@ impl LinkComponent {
         )
     }
 }
+
+/// Readable name of a feed mime type.
+fn feed_kind(mime_type: &str) -> &str {
+    match mime_type {
+        "application/rss+xml" | "application/rdf+xml" => "RSS",
+        "application/atom+xml" => "Atom",
+        "application/feed+json" | "application/json" => "JSON Feed",
+        other => other,
+    }
+}
+
+/// Lets links be dropped on the element.
+fn allow_drop() -> Callback<DragEvent> {
+    Callback::from(|e: DragEvent| e.prevent_default())
+}
+
+mod bulk;
+mod discover;
+pub mod validate;
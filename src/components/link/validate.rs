@@ -0,0 +1,55 @@
+use crate::dto::LinkDto;
+
+/// Checks what was typed into the link input and returns the url to subscribe to.
+pub fn validate_feed_url(input: &str, links: &[LinkDto]) -> Result<String, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("Enter the url of a feed".to_string());
+    }
+    if input.chars().any(char::is_whitespace) {
+        return Err(format!("\"{}\" is not a url", input));
+    }
+    let url = if input.contains("://") {
+        input.to_string()
+    } else {
+        format!("https://{}", input)
+    };
+    let (scheme, rest) = url.split_once("://").unwrap_or(("", ""));
+    if !scheme.eq_ignore_ascii_case("http") && !scheme.eq_ignore_ascii_case("https") {
+        return Err("Only http and https feeds are supported".to_string());
+    }
+    let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host = host.rsplit('@').next().unwrap_or("");
+    let hostname = host.split(':').next().unwrap_or("");
+    if hostname.is_empty() || !(hostname.contains('.') || hostname == "localhost") {
+        return Err(format!("\"{}\" is not a url", input));
+    }
+    let normalized = normalize_url(&url);
+    if links
+        .iter()
+        .any(|link| normalize_url(&link.link) == normalized)
+    {
+        return Err("You are already subscribed to this feed".to_string());
+    }
+    Ok(url)
+}
+
+/// Lowercases scheme and host and drops a trailing slash so equal urls compare equal.
+pub fn normalize_url(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    match url.split_once("://") {
+        Some((scheme, rest)) => {
+            let (host, path) = match rest.find(['/', '?', '#']) {
+                Some(index) => rest.split_at(index),
+                None => (rest, ""),
+            };
+            format!(
+                "{}://{}{}",
+                scheme.to_ascii_lowercase(),
+                host.to_ascii_lowercase(),
+                path
+            )
+        }
+        None => url.to_string(),
+    }
+}
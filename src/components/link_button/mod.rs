@@ -1,85 +1,287 @@
-use crate::api::remove_link;
-use yew::{html, Component, Context, Html, Properties};
+use crate::api::{ApiClient, ApiError};
+use crate::components::feed_icon::FeedIcon;
+use crate::components::link::validate::validate_feed_url;
+use crate::dto::{LinkDto, LinkUpdateDto};
+use crate::sanitize::{safe_url, LINK_SCHEMES};
+use crate::store::LinkStore;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlInputElement};
+use yew::events::DragEvent;
+use yew::{classes, html, Callback, Component, Context, Event, Html, Properties};
+use yewdux::dispatch::{Dispatch, Dispatcher};
+use yewdux::prelude::BasicStore;
 
 #[derive(PartialEq, Properties)]
 pub struct LinkProps {
-    pub link: LinkData,
+    pub link: LinkDto,
+    pub client: ApiClient,
+    /// Called with the event when the user starts dragging the link.
+    #[prop_or_default]
+    pub ondragstart: Callback<DragEvent>,
+    /// Called with the `link_id` when the user deletes the link.
+    pub onremove: Callback<String>,
+    #[prop_or_default]
+    pub selected: bool,
+    /// Called with the `link_id` when the user toggles the selection checkbox.
+    #[prop_or_default]
+    pub onselect: Callback<String>,
 }
 
 pub enum LinkMessage {
-    Remove,
-    Success(bool),
-}
-
-#[derive(Clone, PartialEq)]
-pub struct LinkData {
-    pub link_id: String,
-    pub link: String,
-    pub token: String,
+    LinkState(Rc<LinkStore>),
+    Edit,
+    InputUrl(String),
+    InputName(String),
+    TogglePaused,
+    Save,
+    CancelEdit,
+    Updated(LinkDto),
+    /// The update failed; carries the link as it was before.
+    UpdateFailed(LinkDto, ApiError),
 }
 
 pub enum LinkState {
     Visible,
-    None,
+    Editing(LinkEdit),
+}
+
+/// Values of the edit form.
+pub struct LinkEdit {
+    pub url: String,
+    pub name: String,
+    pub paused: bool,
 }
 
 pub struct Link {
+    link_dispatch: Dispatch<BasicStore<LinkStore>>,
+    links: Vec<LinkDto>,
     state: LinkState,
+    error: Option<String>,
 }
 
 impl Component for Link {
     type Message = LinkMessage;
     type Properties = LinkProps;
 
-    fn create(_ctx: &Context<Self>) -> Self {
+    fn create(ctx: &Context<Self>) -> Self {
         Self {
+            link_dispatch: Dispatch::bridge_state(ctx.link().callback(LinkMessage::LinkState)),
+            links: vec![],
             state: LinkState::Visible,
+            error: None,
         }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
-            LinkMessage::Remove => {
-                let token = ctx.props().link.token.clone();
-                let link_id = ctx.props().link.link_id.clone();
-                ctx.link().send_future(async {
-                    match remove_link(token, link_id).await {
-                        Ok(data) => LinkMessage::Success(data),
-                        Err(_) => LinkMessage::Success(false),
-                    }
+            LinkMessage::LinkState(state) => {
+                self.links = state.links.clone();
+                false
+            }
+            LinkMessage::Edit => {
+                let link = &ctx.props().link;
+                self.error = None;
+                self.state = LinkState::Editing(LinkEdit {
+                    url: link.link.clone(),
+                    name: link.name.clone().unwrap_or_default(),
+                    paused: link.paused,
                 });
                 true
             }
-            LinkMessage::Success(data) => {
-                if data {
-                    self.state = LinkState::None
+            LinkMessage::InputUrl(url) => {
+                if let LinkState::Editing(edit) = &mut self.state {
+                    edit.url = url;
+                }
+                false
+            }
+            LinkMessage::InputName(name) => {
+                if let LinkState::Editing(edit) = &mut self.state {
+                    edit.name = name;
+                }
+                false
+            }
+            LinkMessage::TogglePaused => {
+                if let LinkState::Editing(edit) = &mut self.state {
+                    edit.paused = !edit.paused;
                 }
                 true
             }
+            LinkMessage::Save => {
+                let edit = match &self.state {
+                    LinkState::Editing(edit) => edit,
+                    _ => return false,
+                };
+                let link = ctx.props().link.clone();
+                let update = match self.changes(&link, edit) {
+                    Ok(update) => update,
+                    Err(error) => {
+                        self.error = Some(error);
+                        return true;
+                    }
+                };
+                self.state = LinkState::Visible;
+                self.error = None;
+                if update.is_empty() {
+                    return true;
+                }
+                let updated = update.apply(&link);
+                self.link_dispatch.reduce(move |s| s.replace(updated));
+                let client = ctx.props().client.clone();
+                ctx.link().send_future(async move {
+                    match client.update_link(link.link_id.clone(), &update).await {
+                        Ok(data) => LinkMessage::Updated(data),
+                        Err(error) => LinkMessage::UpdateFailed(link, error),
+                    }
+                });
+                true
+            }
+            LinkMessage::CancelEdit => {
+                self.state = LinkState::Visible;
+                self.error = None;
+                true
+            }
+            LinkMessage::Updated(link) => {
+                self.link_dispatch.reduce(move |s| s.replace(link));
+                false
+            }
+            LinkMessage::UpdateFailed(link, error) => {
+                self.error = Some(match error {
+                    ApiError::Conflict(_) => "You are already subscribed to this feed".to_string(),
+                    error => format!("Changes were not saved: {}", error),
+                });
+                self.link_dispatch.reduce(move |s| s.replace(link));
+                true
+            }
         }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        match self.state {
+        let link = &ctx.props().link;
+        let remove = {
+            let onremove = ctx.props().onremove.clone();
+            let link_id = link.link_id.clone();
+            Callback::from(move |_| onremove.emit(link_id.clone()))
+        };
+        let select = {
+            let onselect = ctx.props().onselect.clone();
+            let link_id = link.link_id.clone();
+            Callback::from(move |_| onselect.emit(link_id.clone()))
+        };
+        // The site url comes from the feed itself, so it is not trusted.
+        let href = link
+            .site_url
+            .as_deref()
+            .and_then(|url| safe_url(url, LINK_SCHEMES))
+            .unwrap_or_else(|| link.link.clone());
+        match &self.state {
             LinkState::Visible => {
                 html! (
-                    <li class = "link">
+                    <li class = {classes!("link", link.paused.then_some("link-paused"))} draggable = "true" ondragstart={ctx.props().ondragstart.clone()}>
                         <div class = "link-main">
                             <div class = "link-info row-direction">
-                               <button class="button-icon" onclick={ctx.link().callback(|_| LinkMessage::Remove)}>
+                                <input class="primary-checkbox link-select" type="checkbox" title="select"
+                                    checked={ctx.props().selected} onchange={select}/>
+                               <button class="button-icon" title="delete" onclick={remove}>
                                     <i class="fa-regular fa-trash-can link-trash"></i>
                                 </button>
+                                <button class="button-icon" title="edit" onclick={ctx.link().callback(|_| LinkMessage::Edit)}>
+                                    <i class="fa-solid fa-pen"></i>
+                                </button>
+                                <FeedIcon url={link.icon()}/>
                                 <div class = "link-description">
-                                    <a target = "_blank" class = "link-href-content" href={ctx.props().link.link.clone()}>{ctx.props().link.link.clone()}</a>
+                                    <a target = "_blank" rel = "noopener noreferrer" class = "link-href-content" href={href}>{link.display_name()}</a>
+                                    {
+                                        if link.paused {
+                                            html!(<span class = "link-paused-label">{" paused"}</span>)
+                                        } else {
+                                            html!()
+                                        }
+                                    }
+                                    <div class = "link-meta">
+                                        {link.link.clone()}
+                                        {
+                                            match &link.last_fetched {
+                                                Some(date) => html!(<span class = "link-fetched">{format!(" · updated {}", date)}</span>),
+                                                None => html!(),
+                                            }
+                                        }
+                                    </div>
+                                    {self.html_error()}
                                 </div>
                             </div>
                         </div>
                     </li>
                 )
             }
-            LinkState::None => {
-                html!()
-            }
+            LinkState::Editing(edit) => self.html_edit(ctx, edit),
+        }
+    }
+}
+
+impl Link {
+    /// Fields of the form that differ from the link.
+    fn changes(&self, link: &LinkDto, edit: &LinkEdit) -> Result<LinkUpdateDto, String> {
+        let mut update = LinkUpdateDto::default();
+        if edit.url.trim() != link.link {
+            let others: Vec<LinkDto> = self
+                .links
+                .iter()
+                .filter(|other| other.link_id != link.link_id)
+                .cloned()
+                .collect();
+            update.link = Some(validate_feed_url(&edit.url, &others)?);
         }
+        let name = edit.name.trim();
+        if name != link.name.as_deref().unwrap_or("") {
+            update.name = Some(name.to_string());
+        }
+        if edit.paused != link.paused {
+            update.paused = Some(edit.paused);
+        }
+        Ok(update)
     }
+
+    fn html_edit(&self, ctx: &Context<Self>, edit: &LinkEdit) -> Html {
+        let change_url = ctx
+            .link()
+            .batch_callback(|e: Event| input_value(e).map(LinkMessage::InputUrl));
+        let change_name = ctx
+            .link()
+            .batch_callback(|e: Event| input_value(e).map(LinkMessage::InputName));
+        html!(
+            <li class = "link link-editing">
+                <div class = "link-edit column-direction">
+                    <label class="primary-input-label">{"Feed url"}</label>
+                    <input class="primary-input" type="text" value={edit.url.clone()} onchange={change_url}/>
+                    <label class="primary-input-label">{"Display name"}</label>
+                    <input class="primary-input" type="text" placeholder={ctx.props().link.title.clone().unwrap_or_default()}
+                        value={edit.name.clone()} onchange={change_name}/>
+                    <label class="primary-input-label">
+                        <input class="primary-checkbox" type="checkbox" checked={edit.paused}
+                            onchange={ctx.link().callback(|_| LinkMessage::TogglePaused)}/>
+                        {" paused"}
+                    </label>
+                    {self.html_error()}
+                    <div class = "row-direction link-edit-actions">
+                        <button class="primary-button" onclick={ctx.link().callback(|_| LinkMessage::Save)}>{"save"}</button>
+                        <button class="primary-button" onclick={ctx.link().callback(|_| LinkMessage::CancelEdit)}>{"cancel"}</button>
+                    </div>
+                </div>
+            </li>
+        )
+    }
+
+    fn html_error(&self) -> Html {
+        match &self.error {
+            Some(error) => html!(<div class = "error-message">{error}</div>),
+            None => html!(),
+        }
+    }
+}
+
+fn input_value(e: Event) -> Option<String> {
+    let target: Option<EventTarget> = e.target();
+    let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+    input.map(|input| input.value())
 }
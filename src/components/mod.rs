@@ -1,5 +1,14 @@
+pub mod article;
 pub mod auth_alert;
+pub mod feed_icon;
+pub mod feed_sidebar;
 pub mod link;
 pub mod link_button;
 pub mod nav;
 pub mod news;
+pub mod opml;
+pub mod player;
+pub mod search;
+pub mod session;
+pub mod shortcuts;
+pub mod starred;
@@ -1,6 +1,8 @@
-use crate::router::Route;
+use crate::router::{PageQuery, Route};
 use crate::store::{AuthState, UserStore};
 use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlInputElement};
 use yew::prelude::*;
 use yew_router::prelude::*;
 use yewdux::dispatch::{Dispatch, Dispatcher};
@@ -9,11 +11,14 @@ use yewdux::prelude::BasicStore;
 pub enum NavMessage {
     UserState(Rc<UserStore>),
     Exit,
+    InputSearch(String),
+    Search,
 }
 
 pub struct NavComponent {
     dispatch: Dispatch<BasicStore<UserStore>>,
     state: Rc<UserStore>,
+    search: String,
 }
 impl Component for NavComponent {
     type Message = NavMessage;
@@ -24,21 +29,39 @@ impl Component for NavComponent {
         Self {
             dispatch,
             state: Default::default(),
+            search: ctx
+                .link()
+                .location()
+                .and_then(|location| location.query::<PageQuery>().ok())
+                .and_then(|query| query.q)
+                .unwrap_or_default(),
         }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             NavMessage::UserState(state) => {
                 self.state = state;
                 true
             }
             NavMessage::Exit => {
-                self.dispatch.reduce(|s| s.auth_state = AuthState::UnAuth);
-                self.dispatch.reduce(|s| s.token = "".to_string());
-                self.dispatch.reduce(|s| s.user_id = "".to_string());
+                self.dispatch.reduce(|s| s.sign_out());
                 true
             }
+            NavMessage::InputSearch(search) => {
+                self.search = search;
+                false
+            }
+            NavMessage::Search => {
+                let query = PageQuery {
+                    q: Some(self.search.trim().to_string()),
+                    ..PageQuery::default()
+                };
+                if let Some(history) = ctx.link().history() {
+                    let _ = history.push_with_query(Route::Search, query);
+                }
+                false
+            }
         }
     }
 
@@ -60,6 +83,15 @@ impl Component for NavComponent {
                 )
             }
             AuthState::Auth => {
+                let input_search = ctx.link().batch_callback(|e: InputEvent| {
+                    let target: Option<EventTarget> = e.target();
+                    let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+                    input.map(|input| NavMessage::InputSearch(input.value()))
+                });
+                let search = ctx.link().callback(|e: FocusEvent| {
+                    e.prevent_default();
+                    NavMessage::Search
+                });
                 html! (
                     <nav class="main-nav">
                         <div class="main-nav-logo-container">
@@ -68,6 +100,15 @@ impl Component for NavComponent {
                             </Link<Route>>
                         </div>
                         <div class="main-nav-group">
+                            <form class="main-nav-search" onsubmit={search}>
+                                <input class="main-nav-search-input" type="search" placeholder="search"
+                                    aria-label="search articles" value={self.search.clone()} oninput={input_search}/>
+                            </form>
+                            <div class="nav-exit-button">
+                                <Link <Route> classes={"main-nav-link"} to={Route::Starred}>
+                                    <i class="fas fa-star"></i>
+                                </Link<Route>>
+                             </div>
                             <div class="nav-exit-button">
                                 <Link <Route> classes={"main-nav-link"} to={Route::Settings}>
                                     <i class="fas fa-tools"></i>
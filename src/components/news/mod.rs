@@ -1,143 +1,1210 @@
-use crate::api::get_content;
-use crate::dto::ContentDto;
-use crate::store::UserStore;
+use crate::api::{ApiClient, ApiError, ContentQuery, PagedContent};
+use crate::components::feed_icon::FeedIcon;
+use crate::components::feed_sidebar::FeedSidebar;
+use crate::components::player::{format_time, parse_duration};
+use crate::date::{absolute, parse_date, relative, DateGroup, DayBoundaries};
+use crate::dto::{ContentDto, LinkDto, UnreadCountDto};
+use crate::router::{PageQuery, Route};
+use crate::sanitize::sanitize;
+use crate::store::{
+    ContentCache, FolderStore, KeyAction, KeyMatch, PlayerStore, ReadStore, SettingsStore,
+    StarStore, StreamStore, UserStore, PAGE_SIZES,
+};
+use gloo_events::EventListener;
+use gloo_timers::callback::Timeout;
+use js_sys::Date;
+use log::error;
+use read_observer::ReadObserver;
+use sentinel::SentinelObserver;
+use shortcuts::ShortcutsHelp;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::rc::Rc;
-use web_sys::{window, Element};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{window, Element, EventTarget, HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
-use yewdux::dispatch::Dispatch;
+use yew_router::components::Link;
+use yew_router::history::{History, Location};
+use yew_router::scope_ext::{HistoryHandle, RouterScopeExt};
+use yewdux::dispatch::{Dispatch, Dispatcher};
 use yewdux::prelude::BasicStore;
 
+const DAY_MS: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+/// Items scrolled past are sent to the backend in one batch after this delay.
+const READ_FLUSH_MS: u32 = 1500;
+/// Infinite scroll only renders the items near the viewport once it holds more than this.
+const VIRTUALIZE_AFTER: usize = 60;
+/// Viewport heights rendered above and below the visible part of the stream.
+const OVERSCAN: f64 = 1.5;
+/// Height assumed for items that were never rendered.
+const ESTIMATED_HEIGHT: f64 = 320.0;
+/// Time to type the next key of a shortcut like `g s`.
+const KEY_SEQUENCE_MS: u32 = 1000;
+
 pub enum NewsMessage {
     UserState(Rc<UserStore>),
-    Success(Vec<ContentDto>),
+    ReadState(Rc<ReadStore>),
+    StarState(Rc<StarStore>),
+    FolderState(Rc<FolderStore>),
+    PlayerState(Rc<PlayerStore>),
+    SettingsState(Rc<SettingsStore>),
+    /// Page that replaces the stream, tagged with the load that asked for it.
+    Success(u32, PagedContent),
+    LoadFailed(u32, ApiError),
+    Reload,
+    /// Next page of the infinite stream, tagged with the load it continues.
+    Appended(u32, Vec<ContentDto>),
+    LoadMoreFailed(u32, ApiError),
+    LoadMore,
+    RetryLoadMore,
+    Scrolled,
+    ToggleInfiniteScroll,
+    Links(Vec<LinkDto>),
+    UnreadCounts(Vec<UnreadCountDto>),
     Next,
     Back,
+    /// The url changed, possibly to another page.
+    Navigated,
+    InputPageSize(u32),
+    ScrolledPast(String),
+    FlushRead,
+    MarkRead(Vec<String>),
+    ToggleRead(String),
+    ToggleStar(String),
+    ToggleAudio(String),
+    MarkAllRead,
+    MarkOlderRead,
+    InputOlderDays(u32),
+    ToggleUnreadOnly,
+    Key(String),
+    KeysExpired,
+    CloseHelp,
+    SyncFailed(ApiError),
+}
+
+#[derive(PartialEq, Properties)]
+pub struct NewsProps {
+    /// Only show items of this feed.
+    #[prop_or_default]
+    pub feed_id: Option<String>,
+    /// Only show items of the feeds in this folder.
+    #[prop_or_default]
+    pub folder_id: Option<String>,
 }
 
 pub struct NewsComponent {
     _dispatch: Dispatch<BasicStore<UserStore>>,
+    read_dispatch: Dispatch<BasicStore<ReadStore>>,
+    star_dispatch: Dispatch<BasicStore<StarStore>>,
+    _folder_dispatch: Dispatch<BasicStore<FolderStore>>,
+    player_dispatch: Dispatch<BasicStore<PlayerStore>>,
+    settings_dispatch: Dispatch<BasicStore<SettingsStore>>,
+    stream_dispatch: Dispatch<BasicStore<StreamStore>>,
+    cache_dispatch: Dispatch<BasicStore<ContentCache>>,
     state: Rc<UserStore>,
+    read_state: Rc<ReadStore>,
+    star_state: Rc<StarStore>,
+    folder_state: Rc<FolderStore>,
+    player_state: Rc<PlayerStore>,
+    settings: Rc<SettingsStore>,
     content: Vec<ContentDto>,
+    links: Vec<LinkDto>,
+    unread_counts: Option<Vec<UnreadCountDto>>,
+    /// First item of the page, from the url.
     start: u32,
     take: u32,
+    /// Where the infinite stream continues.
+    next_start: u32,
+    /// Items in the last page before filtering.
+    page_len: u32,
+    total: Option<u32>,
+    unread_only: bool,
+    older_days: u32,
+    /// False once the backend turned out not to track read state.
+    backend_read: bool,
+    pending_read: Vec<String>,
+    flush_timeout: Option<Timeout>,
+    observer: Option<ReadObserver>,
+    /// Bumped by every load that replaces the stream, so late pages are dropped.
+    generation: u32,
+    loading: bool,
+    /// The last page came back short, there is nothing more to load.
+    end_of_stream: bool,
+    /// Why the stream could not be loaded.
+    load_error: Option<String>,
+    /// Why the next page of the infinite stream could not be loaded.
+    load_more_error: Option<String>,
+    sentinel: NodeRef,
+    sentinel_observer: Option<SentinelObserver>,
+    list: NodeRef,
+    /// Rendered height of the items by `content_id`, for the placeholders.
+    heights: HashMap<String, f64>,
+    /// Items rendered in full, the others are placeholders.
+    visible: Option<(usize, usize)>,
+    _scroll_listener: EventListener,
+    _history_listener: Option<HistoryHandle>,
+    /// Item picked with the keyboard.
+    selected: Option<String>,
+    scroll_to_selected: bool,
+    /// Keys of a shortcut that is not complete yet.
+    pending_keys: Vec<String>,
+    keys_timeout: Option<Timeout>,
+    show_help: bool,
+    _key_listener: EventListener,
 }
 impl Component for NewsComponent {
     type Message = NewsMessage;
-    type Properties = ();
+    type Properties = NewsProps;
 
     fn create(ctx: &Context<Self>) -> Self {
         let dispatch = Dispatch::bridge_state(ctx.link().callback(NewsMessage::UserState));
+        let read_dispatch = Dispatch::bridge_state(ctx.link().callback(NewsMessage::ReadState));
+        let star_dispatch = Dispatch::bridge_state(ctx.link().callback(NewsMessage::StarState));
+        let folder_dispatch = Dispatch::bridge_state(ctx.link().callback(NewsMessage::FolderState));
+        let player_dispatch = Dispatch::bridge_state(ctx.link().callback(NewsMessage::PlayerState));
+        let settings_dispatch =
+            Dispatch::bridge_state(ctx.link().callback(NewsMessage::SettingsState));
+        let scrolled = ctx.link().callback(|_| NewsMessage::Scrolled);
+        let settings: Rc<SettingsStore> = Default::default();
+        let (start, take) = Self::page(ctx, &settings);
         Self {
             _dispatch: dispatch,
-            state: Default::default(),
+            read_dispatch,
+            star_dispatch,
+            _folder_dispatch: folder_dispatch,
+            player_dispatch,
+            settings_dispatch,
+            stream_dispatch: Dispatch::new(),
+            cache_dispatch: Dispatch::new(),
+            // Signed out until the store reports, so its first state loads the stream.
+            state: Rc::new(UserStore {
+                token: String::new(),
+                ..Default::default()
+            }),
+            read_state: Default::default(),
+            star_state: Default::default(),
+            folder_state: Default::default(),
+            player_state: Default::default(),
+            settings,
             content: vec![],
-            start: 0,
-            take: 15,
+            links: vec![],
+            unread_counts: None,
+            start,
+            take,
+            next_start: start,
+            page_len: 0,
+            total: None,
+            unread_only: false,
+            older_days: 7,
+            backend_read: true,
+            pending_read: vec![],
+            flush_timeout: None,
+            observer: ReadObserver::new(ctx.link().callback(NewsMessage::ScrolledPast)),
+            generation: 0,
+            loading: false,
+            end_of_stream: false,
+            load_error: None,
+            load_more_error: None,
+            sentinel: NodeRef::default(),
+            sentinel_observer: SentinelObserver::new(
+                ctx.link().callback(|_| NewsMessage::LoadMore),
+            ),
+            list: NodeRef::default(),
+            heights: HashMap::new(),
+            visible: None,
+            _scroll_listener: EventListener::new(&gloo_utils::window(), "scroll", move |_| {
+                scrolled.emit(())
+            }),
+            _history_listener: ctx
+                .link()
+                .add_history_listener(ctx.link().callback(|_| NewsMessage::Navigated)),
+            selected: None,
+            scroll_to_selected: false,
+            pending_keys: vec![],
+            keys_timeout: None,
+            show_help: false,
+            _key_listener: shortcuts::listen(ctx.link().callback(NewsMessage::Key)),
         }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             NewsMessage::UserState(state) => {
+                // Token refreshes keep the stream, only signing in loads it.
+                let signed_in = !self.state.has_token() && state.has_token();
                 self.state = state;
-                if self.state.token.is_empty() || self.state.token.eq("error") {
+                if signed_in {
+                    self.load(ctx);
+                    self.load_links(ctx);
+                }
+                true
+            }
+            NewsMessage::ReadState(state) => {
+                self.read_state = state;
+                true
+            }
+            NewsMessage::StarState(state) => {
+                self.star_state = state;
+                true
+            }
+            NewsMessage::FolderState(state) => {
+                let folder_id = ctx.props().folder_id.as_deref();
+                let reload = folder_id
+                    .is_some_and(|id| self.folder_state.link_ids(id) != state.link_ids(id));
+                self.folder_state = state;
+                if reload && !self.state.token.is_empty() {
+                    self.load(ctx);
+                }
+                true
+            }
+            NewsMessage::PlayerState(state) => {
+                self.player_state = state;
+                true
+            }
+            NewsMessage::SettingsState(state) => {
+                let restart = self.settings.infinite_scroll != state.infinite_scroll;
+                self.settings = state;
+                if self.state.token.is_empty() {
                     return true;
                 }
-                let token = self.state.token.clone();
-                let start = self.start;
-                let take = self.take;
-                ctx.link().send_future(async move {
-                    match get_content(token, start, take).await {
-                        Ok(data) => NewsMessage::Success(data),
-                        Err(_) => NewsMessage::Success(vec![]),
-                    }
-                });
+                if restart {
+                    self.go_to(ctx, 0, Self::url_query(ctx).take);
+                } else {
+                    self.follow_url(ctx);
+                }
+                true
+            }
+            NewsMessage::ToggleInfiniteScroll => {
+                self.settings_dispatch
+                    .reduce(|s| s.toggle_infinite_scroll());
+                false
+            }
+            NewsMessage::LoadMore => {
+                if !self.settings.infinite_scroll
+                    || self.loading
+                    || self.end_of_stream
+                    || self.load_more_error.is_some()
+                {
+                    return false;
+                }
+                self.next_start += self.take;
+                self.load_more(ctx);
+                true
+            }
+            NewsMessage::RetryLoadMore => {
+                self.load_more(ctx);
+                true
+            }
+            NewsMessage::LoadMoreFailed(generation, error) => {
+                if generation != self.generation {
+                    return false;
+                }
+                self.loading = false;
+                self.load_more_error = Some(error.to_string());
+                true
+            }
+            NewsMessage::Appended(generation, content) => {
+                if generation != self.generation {
+                    return false;
+                }
+                self.loading = false;
+                self.end_of_stream = (content.len() as u32) < self.take;
+                let mut content = self.filter(ctx, content);
+                let known: HashSet<&str> = self
+                    .content
+                    .iter()
+                    .map(|el| el.content_id.as_str())
+                    .collect();
+                content.retain(|el| !known.contains(el.content_id.as_str()));
+                self.cache(&content);
+                self.content.extend(content);
+                self.visible = self.visible_range();
+                self.publish_stream(ctx);
+                true
+            }
+            NewsMessage::Scrolled => {
+                let visible = self.visible_range();
+                if visible == self.visible {
+                    return false;
+                }
+                self.visible = visible;
                 true
             }
             NewsMessage::Next => {
-                self.start += 15;
-                let take = self.take;
-                let start = self.start;
-                let token = self.state.token.clone();
-                ctx.link().send_future(async move {
-                    match get_content(token, start, take).await {
-                        Ok(data) => NewsMessage::Success(data),
-                        Err(_) => NewsMessage::Success(vec![]),
-                    }
-                });
+                if self.end_of_stream {
+                    return false;
+                }
+                self.go_to(ctx, self.start + self.take, Self::url_query(ctx).take);
                 false
             }
             NewsMessage::Back => {
-                if self.start >= 15 {
-                    self.start -= 15;
-                }
-                let take = self.take;
-                let start = self.start;
-                let token = self.state.token.clone();
-                ctx.link().send_future(async move {
-                    match get_content(token, start, take).await {
-                        Ok(data) => NewsMessage::Success(data),
-                        Err(_) => NewsMessage::Success(vec![]),
-                    }
-                });
+                if self.start == 0 {
+                    return false;
+                }
+                let start = self.start.saturating_sub(self.take);
+                self.go_to(ctx, start, Self::url_query(ctx).take);
+                false
+            }
+            NewsMessage::Navigated => {
+                // Leaving the stream notifies too, before this component goes away.
+                let stream = matches!(
+                    ctx.link().route::<Route>(),
+                    Some(Route::Home | Route::Feed { .. } | Route::Folder { .. })
+                );
+                if stream && !self.state.token.is_empty() {
+                    self.follow_url(ctx);
+                }
                 false
             }
-            NewsMessage::Success(content) => {
+            NewsMessage::InputPageSize(page_size) => {
+                self.settings_dispatch
+                    .reduce(move |s| s.set_page_size(page_size));
+                self.go_to(ctx, 0, None);
+                false
+            }
+            NewsMessage::Success(generation, page) => {
+                if generation != self.generation {
+                    return false;
+                }
                 window().unwrap().scroll_to_with_x_and_y(0.0, 0.0);
-                self.content = content;
+                self.loading = false;
+                self.page_len = page.items.len() as u32;
+                self.end_of_stream = self.page_len < self.take;
+                self.next_start = self.start;
+                self.total = page.total;
+                self.content = self.filter(ctx, page.items);
+                self.cache(&self.content);
+                self.selected = None;
+                self.heights.clear();
+                self.visible = self.visible_range();
+                self.publish_stream(ctx);
+                self.load_unread_counts(ctx);
+                true
+            }
+            NewsMessage::LoadFailed(generation, error) => {
+                if generation != self.generation {
+                    return false;
+                }
+                self.loading = false;
+                self.load_error = Some(error.to_string());
+                self.content.clear();
+                self.page_len = 0;
+                self.total = None;
+                self.visible = None;
+                true
+            }
+            NewsMessage::Reload => {
+                self.load(ctx);
+                true
+            }
+            NewsMessage::Links(links) => {
+                self.links = links;
+                true
+            }
+            NewsMessage::UnreadCounts(counts) => {
+                self.unread_counts = Some(counts);
+                true
+            }
+            NewsMessage::ScrolledPast(id) => {
+                let read = self
+                    .content
+                    .iter()
+                    .find(|el| el.content_id == id)
+                    .map(|el| self.read_state.is_read(el))
+                    .unwrap_or(true);
+                if read || self.pending_read.contains(&id) {
+                    return false;
+                }
+                self.pending_read.push(id);
+                if self.flush_timeout.is_none() {
+                    let flush = ctx.link().callback(|_| NewsMessage::FlushRead);
+                    self.flush_timeout = Some(Timeout::new(READ_FLUSH_MS, move || flush.emit(())));
+                }
+                false
+            }
+            NewsMessage::FlushRead => {
+                self.flush_timeout = None;
+                let ids = std::mem::take(&mut self.pending_read);
+                ctx.link().send_message(NewsMessage::MarkRead(ids));
+                false
+            }
+            NewsMessage::MarkRead(ids) => {
+                if ids.is_empty() {
+                    return false;
+                }
+                let local_ids = ids.clone();
+                self.read_dispatch.reduce(move |s| s.mark_read(&local_ids));
+                if self.backend_read {
+                    let client = ApiClient::new(&self.state);
+                    ctx.link().send_future(async move {
+                        match client.mark_read(ids).await {
+                            Ok(_) => match client.get_unread_counts().await {
+                                Ok(counts) => NewsMessage::UnreadCounts(counts),
+                                Err(error) => NewsMessage::SyncFailed(error),
+                            },
+                            Err(error) => NewsMessage::SyncFailed(error),
+                        }
+                    });
+                }
+                false
+            }
+            NewsMessage::ToggleRead(id) => {
+                let read = self
+                    .content
+                    .iter()
+                    .find(|el| el.content_id == id)
+                    .map(|el| self.read_state.is_read(el))
+                    .unwrap_or(false);
+                if !read {
+                    ctx.link().send_message(NewsMessage::MarkRead(vec![id]));
+                    return false;
+                }
+                let ids = vec![id];
+                let local_ids = ids.clone();
+                self.read_dispatch
+                    .reduce(move |s| s.mark_unread(&local_ids));
+                if self.backend_read {
+                    let client = ApiClient::new(&self.state);
+                    ctx.link().send_future(async move {
+                        match client.mark_unread(ids).await {
+                            Ok(_) => match client.get_unread_counts().await {
+                                Ok(counts) => NewsMessage::UnreadCounts(counts),
+                                Err(error) => NewsMessage::SyncFailed(error),
+                            },
+                            Err(error) => NewsMessage::SyncFailed(error),
+                        }
+                    });
+                }
+                false
+            }
+            NewsMessage::ToggleStar(id) => {
+                if let Some(content) = self.content.iter().find(|el| el.content_id == id) {
+                    let content = content.clone();
+                    self.star_dispatch.reduce(move |s| s.toggle(&content));
+                }
+                false
+            }
+            NewsMessage::ToggleAudio(id) => {
+                if let Some(content) = self.content.iter().find(|el| el.content_id == id) {
+                    let content = content.clone();
+                    self.player_dispatch.reduce(move |s| s.toggle(&content));
+                }
+                false
+            }
+            NewsMessage::MarkAllRead => {
+                let ids: Vec<String> = self
+                    .content
+                    .iter()
+                    .map(|el| el.content_id.clone())
+                    .collect();
+                self.read_dispatch.reduce(move |s| s.mark_read(&ids));
+                self.mark_read_before(ctx, Date::now(), None);
+                false
+            }
+            NewsMessage::MarkOlderRead => {
+                let before = Date::now() - self.older_days as f64 * DAY_MS;
+                let iso = Date::new(&JsValue::from_f64(before)).to_iso_string();
+                self.mark_read_before(ctx, before, Some(iso.into()));
+                false
+            }
+            NewsMessage::InputOlderDays(days) => {
+                self.older_days = days;
+                true
+            }
+            NewsMessage::ToggleUnreadOnly => {
+                self.unread_only = !self.unread_only;
+                self.go_to(ctx, 0, Self::url_query(ctx).take);
+                true
+            }
+            NewsMessage::Key(key) => {
+                if self.show_help && key == "Escape" {
+                    self.show_help = false;
+                    return true;
+                }
+                let mut keys = std::mem::take(&mut self.pending_keys);
+                keys.push(key.clone());
+                match self.settings.match_keys(&keys) {
+                    KeyMatch::Action(action) => {
+                        self.keys_timeout = None;
+                        self.shortcut(ctx, action)
+                    }
+                    KeyMatch::Prefix => {
+                        self.pending_keys = keys;
+                        let expire = ctx.link().callback(|_| NewsMessage::KeysExpired);
+                        self.keys_timeout =
+                            Some(Timeout::new(KEY_SEQUENCE_MS, move || expire.emit(())));
+                        false
+                    }
+                    KeyMatch::None => {
+                        self.keys_timeout = None;
+                        if keys.len() > 1 {
+                            // The key may start a shortcut of its own.
+                            ctx.link().send_message(NewsMessage::Key(key));
+                        }
+                        false
+                    }
+                }
+            }
+            NewsMessage::KeysExpired => {
+                self.keys_timeout = None;
+                self.pending_keys.clear();
+                false
+            }
+            NewsMessage::CloseHelp => {
+                self.show_help = false;
                 true
             }
+            NewsMessage::SyncFailed(error) => {
+                if error.is_unsupported() {
+                    self.backend_read = false;
+                    self.unread_counts = None;
+                    return true;
+                }
+                error!("read state sync failed: {}", error);
+                false
+            }
         }
     }
 
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        (self.start, self.take) = Self::page(ctx, &self.settings);
+        self.load(ctx);
+        true
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
         html!(
-            <div class="container-content">
-                <ul class="content-list">
-                    <ul>{self.get_content()}</ul>
-                </ul>
-                {
-                    if self.content.is_empty() {
-                        html!(<div></div>)
-                    } else {
-                        {self.get_paging(ctx)}
+            <div class="news-layout">
+                <FeedSidebar links={self.links.clone()} counts={self.unread_by_feed()}
+                    folders={self.folder_state.clone()}
+                    active={ctx.props().feed_id.clone()}
+                    active_folder={ctx.props().folder_id.clone()}/>
+                <div class="container-content">
+                    {self.get_toolbar(ctx)}
+                    {
+                        if self.show_help {
+                            html!(<ShortcutsHelp settings={self.settings.clone()}
+                                onclose={ctx.link().callback(|_| NewsMessage::CloseHelp)}/>)
+                        } else {
+                            html!()
+                        }
                     }
-                }
+                    {
+                        if let Some(error) = &self.load_error {
+                            html!(
+                                <div class="content-error">
+                                    <p class="error-message">{error}</p>
+                                    <button type="button" class="content-error-button"
+                                        onclick={ctx.link().callback(|_| NewsMessage::Reload)}>{"retry"}</button>
+                                </div>
+                            )
+                        } else {
+                            html!()
+                        }
+                    }
+                    <ul class="content-list">
+                        <ul ref={self.list.clone()}>{self.get_content(ctx)}</ul>
+                    </ul>
+                    {
+                        if self.settings.infinite_scroll {
+                            self.get_stream_end(ctx)
+                        } else if self.content.is_empty() && self.start == 0 {
+                            html!(<div></div>)
+                        } else {
+                            {self.get_paging(ctx)}
+                        }
+                    }
+                </div>
             </div>
         )
     }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if let Some(observer) = &self.observer {
+            observer.observe_all(self.list.cast::<Element>());
+        }
+        if let Some(observer) = &self.sentinel_observer {
+            observer.observe(self.sentinel.cast::<Element>());
+        }
+        self.measure_heights();
+        if std::mem::take(&mut self.scroll_to_selected) {
+            let selected = self
+                .list
+                .cast::<Element>()
+                .and_then(|list| list.query_selector(".content-selected").ok().flatten());
+            if let Some(selected) = selected {
+                selected.scroll_into_view_with_bool(true);
+            }
+        }
+    }
 }
 
 impl NewsComponent {
-    fn get_content(&self) -> Html {
+    fn query(&self, ctx: &Context<Self>, start: u32) -> ContentQuery {
+        ContentQuery {
+            start,
+            take: self.take,
+            unread_only: self.unread_only,
+            link_ids: self.link_ids(ctx),
+        }
+    }
+
+    fn url_query(ctx: &Context<Self>) -> PageQuery {
+        ctx.link()
+            .location()
+            .and_then(|location| location.query::<PageQuery>().ok())
+            .unwrap_or_default()
+    }
+
+    /// Start and size of the page asked for in the url.
+    fn page(ctx: &Context<Self>, settings: &SettingsStore) -> (u32, u32) {
+        let query = Self::url_query(ctx);
+        let take = query.take.unwrap_or(settings.page_size).clamp(1, 100);
+        (query.start, take)
+    }
+
+    /// Loads the page in the url unless it is the one already shown.
+    fn follow_url(&mut self, ctx: &Context<Self>) {
+        let page = Self::page(ctx, &self.settings);
+        if page != (self.start, self.take) {
+            (self.start, self.take) = page;
+            self.load(ctx);
+        }
+    }
+
+    /// Moves to the page at `start` through the url, so it survives a reload.
+    fn go_to(&mut self, ctx: &Context<Self>, start: u32, take: Option<u32>) {
+        let query = PageQuery {
+            start,
+            take,
+            q: None,
+        };
+        let (history, route) = match (ctx.link().history(), ctx.link().route::<Route>()) {
+            (Some(history), Some(route)) => (history, route),
+            _ => {
+                self.start = start;
+                self.load(ctx);
+                return;
+            }
+        };
+        if query == Self::url_query(ctx) {
+            self.load(ctx);
+        } else if query == PageQuery::default() {
+            history.push(route);
+        } else if history.push_with_query(route, query).is_err() {
+            self.start = start;
+            self.load(ctx);
+        }
+    }
+
+    /// Hands the loaded items to the reader view for its previous and next links.
+    fn publish_stream(&self, ctx: &Context<Self>) {
+        let items = self.content.clone();
+        let path = ctx
+            .link()
+            .location()
+            .map(|location| location.pathname())
+            .unwrap_or_else(|| "/".to_string());
+        let page = Self::url_query(ctx);
+        self.stream_dispatch
+            .reduce(move |s| s.set(items, path, page));
+    }
+
+    /// Keeps the items for searching without the backend.
+    fn cache(&self, items: &[ContentDto]) {
+        if items.is_empty() {
+            return;
+        }
+        let items = items.to_vec();
+        self.cache_dispatch.reduce(move |s| s.add(&items));
+    }
+
+    /// Runs a keyboard shortcut, returns whether the view changed.
+    fn shortcut(&mut self, ctx: &Context<Self>, action: KeyAction) -> bool {
+        let selected = self.selected.clone();
+        match action {
+            KeyAction::NextItem => self.move_selection(ctx, 1),
+            KeyAction::PreviousItem => self.move_selection(ctx, -1),
+            KeyAction::Open => {
+                let content =
+                    selected.and_then(|id| self.content.iter().find(|el| el.content_id == id));
+                if let Some(content) = content {
+                    if let Some(window) = window() {
+                        let _ = window.open_with_url_and_target(&content.link_url, "_blank");
+                    }
+                    let id = content.content_id.clone();
+                    ctx.link().send_message(NewsMessage::MarkRead(vec![id]));
+                }
+                false
+            }
+            KeyAction::Star => {
+                if let Some(id) = selected {
+                    ctx.link().send_message(NewsMessage::ToggleStar(id));
+                }
+                false
+            }
+            KeyAction::ToggleRead => {
+                if let Some(id) = selected {
+                    ctx.link().send_message(NewsMessage::ToggleRead(id));
+                }
+                false
+            }
+            KeyAction::NextPage if !self.settings.infinite_scroll => {
+                ctx.link().send_message(NewsMessage::Next);
+                false
+            }
+            KeyAction::PreviousPage if !self.settings.infinite_scroll => {
+                ctx.link().send_message(NewsMessage::Back);
+                false
+            }
+            KeyAction::NextPage | KeyAction::PreviousPage => false,
+            KeyAction::GoToSettings => {
+                if let Some(history) = ctx.link().history() {
+                    history.push(Route::Settings);
+                }
+                false
+            }
+            KeyAction::Help => {
+                self.show_help = !self.show_help;
+                true
+            }
+        }
+    }
+
+    /// Selects the item `delta` places away from the selected one.
+    fn move_selection(&mut self, ctx: &Context<Self>, delta: isize) -> bool {
+        let current = self
+            .selected
+            .as_ref()
+            .and_then(|id| self.content.iter().position(|el| &el.content_id == id));
+        let index = match current {
+            Some(index) => index as isize + delta,
+            None => 0,
+        };
+        if index < 0 {
+            return false;
+        }
+        let index = index as usize;
+        if index >= self.content.len() {
+            ctx.link().send_message(NewsMessage::LoadMore);
+            return false;
+        }
+        self.selected = Some(self.content[index].content_id.clone());
+        self.scroll_to_selected = true;
+        if let Some((first, last)) = self.visible {
+            if index < first || index >= last {
+                self.visible = Some((index.saturating_sub(2), (index + 3).min(self.content.len())));
+            }
+        }
+        true
+    }
+
+    /// Feeds of the current view, empty for the stream of all feeds.
+    fn link_ids(&self, ctx: &Context<Self>) -> Vec<String> {
+        match (&ctx.props().feed_id, &ctx.props().folder_id) {
+            (Some(feed_id), _) => vec![feed_id.clone()],
+            (None, Some(folder_id)) => self.folder_state.link_ids(folder_id),
+            (None, None) => vec![],
+        }
+    }
+
+    fn load(&mut self, ctx: &Context<Self>) {
+        self.generation += 1;
+        self.loading = true;
+        self.load_error = None;
+        self.load_more_error = None;
+        if ctx.props().folder_id.is_some() && self.link_ids(ctx).is_empty() {
+            let page = PagedContent {
+                items: vec![],
+                total: Some(0),
+            };
+            ctx.link()
+                .send_message(NewsMessage::Success(self.generation, page));
+            return;
+        }
+        let generation = self.generation;
+        let client = ApiClient::new(&self.state);
+        let query = self.query(ctx, self.start);
+        ctx.link().send_future(async move {
+            match client.get_content(&query).await {
+                Ok(page) => NewsMessage::Success(generation, page),
+                Err(error) => NewsMessage::LoadFailed(generation, error),
+            }
+        });
+    }
+
+    /// Fetches the page at `next_start` and appends it to the stream.
+    fn load_more(&mut self, ctx: &Context<Self>) {
+        self.loading = true;
+        self.load_more_error = None;
+        let generation = self.generation;
+        let client = ApiClient::new(&self.state);
+        let query = self.query(ctx, self.next_start);
+        ctx.link().send_future(async move {
+            match client.get_content(&query).await {
+                Ok(page) => NewsMessage::Appended(generation, page.items),
+                Err(error) => NewsMessage::LoadMoreFailed(generation, error),
+            }
+        });
+    }
+
+    /// Drops the items the current view does not show.
+    fn filter(&self, ctx: &Context<Self>, mut content: Vec<ContentDto>) -> Vec<ContentDto> {
+        if self.unread_only {
+            content.retain(|el| !self.read_state.is_read(el));
+        }
+        let link_ids = self.link_ids(ctx);
+        if !link_ids.is_empty() {
+            content.retain(|el| link_ids.contains(&el.link_id));
+        }
+        content
+    }
+
+    /// Range of items near the viewport, `None` when everything is rendered.
+    fn visible_range(&self) -> Option<(usize, usize)> {
+        if !self.settings.infinite_scroll || self.content.len() <= VIRTUALIZE_AFTER {
+            return None;
+        }
+        let list = self.list.cast::<Element>()?;
+        let viewport = window()?.inner_height().ok()?.as_f64()?;
+        let top = -list.get_bounding_client_rect().top() - viewport * OVERSCAN;
+        let bottom = top + viewport * (1.0 + 2.0 * OVERSCAN);
+        let mut offset = 0.0;
+        let mut range: Option<(usize, usize)> = None;
+        for (index, el) in self.content.iter().enumerate() {
+            let height = self
+                .heights
+                .get(&el.content_id)
+                .copied()
+                .unwrap_or(ESTIMATED_HEIGHT);
+            if offset + height >= top && offset <= bottom {
+                range = Some((range.map_or(index, |(first, _)| first), index + 1));
+            }
+            offset += height;
+        }
+        Some(range.unwrap_or((0, 0)))
+    }
+
+    /// Remembers the height of the rendered items so placeholders keep the layout.
+    fn measure_heights(&mut self) {
+        if !self.settings.infinite_scroll {
+            return;
+        }
+        let list = match self.list.cast::<Element>() {
+            Some(list) => list,
+            None => return,
+        };
+        let elements = match list.query_selector_all(".content-element") {
+            Ok(elements) => elements,
+            Err(_) => return,
+        };
+        for i in 0..elements.length() {
+            let element = match elements.item(i).and_then(|n| n.dyn_into::<Element>().ok()) {
+                Some(element) => element,
+                None => continue,
+            };
+            if let Some(id) = element.get_attribute("data-content-id") {
+                let height = element.get_bounding_client_rect().height();
+                self.heights.insert(id, height);
+            }
+        }
+    }
+
+    fn load_links(&self, ctx: &Context<Self>) {
+        let client = ApiClient::new(&self.state);
+        ctx.link().send_future(async move {
+            match client.get_links().await {
+                Ok(data) => NewsMessage::Links(data),
+                Err(_) => NewsMessage::Links(vec![]),
+            }
+        });
+    }
+
+    fn load_unread_counts(&self, ctx: &Context<Self>) {
+        if !self.backend_read {
+            return;
+        }
+        let client = ApiClient::new(&self.state);
+        ctx.link().send_future(async move {
+            match client.get_unread_counts().await {
+                Ok(counts) => NewsMessage::UnreadCounts(counts),
+                Err(error) => NewsMessage::SyncFailed(error),
+            }
+        });
+    }
+
+    /// Marks the items of the current view published before `before` as read.
+    fn mark_read_before(&self, ctx: &Context<Self>, before: f64, iso: Option<String>) {
+        let link_ids = self.link_ids(ctx);
+        // An empty folder would otherwise mark the whole stream.
+        if ctx.props().folder_id.is_some() && link_ids.is_empty() {
+            return;
+        }
+        let scope = link_ids.clone();
+        self.read_dispatch
+            .reduce(move |s| s.mark_read_before(before, &scope));
+        if !self.backend_read {
+            return;
+        }
+        let client = ApiClient::new(&self.state);
+        ctx.link().send_future(async move {
+            match client.mark_all_read(iso, link_ids).await {
+                Ok(_) => match client.get_unread_counts().await {
+                    Ok(counts) => NewsMessage::UnreadCounts(counts),
+                    Err(error) => NewsMessage::SyncFailed(error),
+                },
+                Err(error) => NewsMessage::SyncFailed(error),
+            }
+        });
+    }
+
+    /// Unread items per feed, from the backend when it tracks read state and
+    /// from the loaded page otherwise.
+    fn unread_by_feed(&self) -> BTreeMap<String, u32> {
+        let mut counts = BTreeMap::new();
+        match &self.unread_counts {
+            Some(unread_counts) => {
+                for count in unread_counts.iter().filter(|count| count.count > 0) {
+                    counts.insert(count.link_id.clone(), count.count);
+                }
+            }
+            None => {
+                for el in self
+                    .content
+                    .iter()
+                    .filter(|el| !self.read_state.is_read(el))
+                {
+                    *counts.entry(el.link_id.clone()).or_insert(0) += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    fn get_toolbar(&self, ctx: &Context<Self>) -> Html {
+        let total: u32 = self.unread_by_feed().values().sum();
+        let change_days = ctx.link().batch_callback(|e: Event| {
+            let target: Option<EventTarget> = e.target();
+            let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+            input.and_then(|input| input.value().parse().ok().map(NewsMessage::InputOlderDays))
+        });
+        let change_page_size = ctx.link().batch_callback(|e: Event| {
+            let target: Option<EventTarget> = e.target();
+            let select = target.and_then(|t| t.dyn_into::<HtmlSelectElement>().ok());
+            select.and_then(|select| select.value().parse().ok().map(NewsMessage::InputPageSize))
+        });
+        html!(
+            <div class="content-toolbar">
+                <div class="content-toolbar-actions">
+                    <span class="content-unread-total">{format!("{} unread", total)}</span>
+                    <label class="content-toolbar-item">
+                        <input type="checkbox" checked={self.unread_only}
+                            onchange={ctx.link().callback(|_| NewsMessage::ToggleUnreadOnly)}/>
+                        {" show unread only"}
+                    </label>
+                    <label class="content-toolbar-item">
+                        <input type="checkbox" checked={self.settings.infinite_scroll}
+                            onchange={ctx.link().callback(|_| NewsMessage::ToggleInfiniteScroll)}/>
+                        {" infinite scroll"}
+                    </label>
+                    <label class="content-toolbar-item">
+                        <select class="content-page-size" onchange={change_page_size}>
+                            {
+                                for PAGE_SIZES.iter().map(|size| html!(
+                                    <option value={size.to_string()} selected={*size == self.take}>{size}</option>
+                                ))
+                            }
+                        </select>
+                        {" per page"}
+                    </label>
+                    <button class="content-toolbar-button" onclick={ctx.link().callback(|_| NewsMessage::MarkAllRead)}>
+                        {"mark all as read"}
+                    </button>
+                    <span class="content-toolbar-item">
+                        {"mark older than "}
+                        <input class="content-days-input" type="number" min="1"
+                            value={self.older_days.to_string()} onchange={change_days}/>
+                        {" days "}
+                        <button class="content-toolbar-button" onclick={ctx.link().callback(|_| NewsMessage::MarkOlderRead)}>
+                            {"as read"}
+                        </button>
+                    </span>
+                </div>
+            </div>
+        )
+    }
+
+    fn get_content(&self, ctx: &Context<Self>) -> Html {
+        let now = Date::now();
+        let days = DayBoundaries::now();
+        let mut group: Option<DateGroup> = None;
         self.content
             .iter()
-            .map(|el| {
+            .enumerate()
+            .map(|(index, el)| {
+                let published = parse_date(&el.date);
+                let header = match published.map(|ms| days.group(ms)) {
+                    Some(current) if group != Some(current) => {
+                        group = Some(current);
+                        html!(<li class="content-group-header">{current.label()}</li>)
+                    }
+                    _ => html!(),
+                };
+                if let Some((first, last)) = self.visible {
+                    if index < first || index >= last {
+                        let height = self
+                            .heights
+                            .get(&el.content_id)
+                            .copied()
+                            .unwrap_or(ESTIMATED_HEIGHT);
+                        return html!(
+                            <>
+                            {header}
+                            <li class="content-placeholder" style={format!("height: {}px", height)}></li>
+                            </>
+                        );
+                    }
+                }
+                let read = self.read_state.is_read(el);
+                let starred = self.star_state.is_starred(&el.content_id);
+                let open_id = el.content_id.clone();
+                let toggle_id = el.content_id.clone();
+                let star_id = el.content_id.clone();
                 html!(
-                    <li class="content-element">
+                    <>
+                    {header}
+                    <li class={classes!("content-element", read.then_some("content-read"),
+                            (self.selected.as_ref() == Some(&el.content_id)).then_some("content-selected"))}
+                        data-content-id={el.content_id.clone()}>
                         <div class="content-title">
-                                <a target="_blank" href={el.link_url.clone()}>{el.title.clone()}</a>
+                                <Link<Route> to={Route::Article { id: el.content_id.clone() }}>
+                                    {el.title.clone()}
+                                </Link<Route>>
+                                <a class="button-icon content-original" target="_blank" rel="noopener noreferrer"
+                                    href={el.link_url.clone()} title="open original"
+                                    onclick={ctx.link().callback(move |_| NewsMessage::MarkRead(vec![open_id.clone()]))}>
+                                    <i class="fa-solid fa-arrow-up-right-from-square"></i>
+                                </a>
+                                <button class="button-icon content-read-toggle"
+                                    title={if read { "mark as unread" } else { "mark as read" }}
+                                    onclick={ctx.link().callback(move |_| NewsMessage::ToggleRead(toggle_id.clone()))}>
+                                    <i class={if read { "fa-regular fa-envelope-open" } else { "fa-solid fa-envelope" }}></i>
+                                </button>
+                                <button class={classes!("button-icon", "content-star", starred.then_some("content-starred"))}
+                                    title={if starred { "unstar" } else { "star" }}
+                                    onclick={ctx.link().callback(move |_| NewsMessage::ToggleStar(star_id.clone()))}>
+                                    <i class={if starred { "fa-solid fa-star" } else { "fa-regular fa-star" }}></i>
+                                </button>
                         </div>
+                        {self.get_meta(el, published, now)}
+                        {self.get_audio(ctx, el)}
                         <div class="content-desc"><SafeHtml html={match &el.description {
                                 Some(desc) => desc.to_string().clone(),
                                 None => "".to_string()
                         }}/></div>
                     </li>
+                    </>
                 )
             })
             .collect::<Html>()
     }
 
+    fn get_audio(&self, ctx: &Context<Self>, el: &ContentDto) -> Html {
+        if el.audio().is_none() {
+            return html!();
+        }
+        let playing = self.player_state.is_playing(&el.content_id);
+        let id = el.content_id.clone();
+        let duration = el.duration.as_deref().and_then(parse_duration);
+        let position = self.player_state.position(&el.content_id);
+        html!(
+            <div class="content-audio">
+                <button class="button-icon content-audio-toggle" title={if playing { "pause" } else { "play" }}
+                    onclick={ctx.link().callback(move |_| NewsMessage::ToggleAudio(id.clone()))}>
+                    <i class={if playing { "fa-solid fa-circle-pause" } else { "fa-solid fa-circle-play" }}></i>
+                </button>
+                {
+                    match (position, duration) {
+                        (Some(position), Some(duration)) => format!("{} of {}", format_time(position), format_time(duration)),
+                        (Some(position), None) => format!("resume at {}", format_time(position)),
+                        (None, Some(duration)) => format_time(duration),
+                        (None, None) => "episode".to_string(),
+                    }
+                }
+            </div>
+        )
+    }
+
+    /// Feed and publication date of an item.
+    fn get_meta(&self, el: &ContentDto, published: Option<f64>, now: f64) -> Html {
+        let feed = match self.links.iter().find(|link| link.link_id == el.link_id) {
+            Some(link) => html!(
+                <>
+                    <FeedIcon url={link.icon()}/>
+                    <span class="content-feed-name">{link.display_name()}</span>
+                </>
+            ),
+            None => html!(),
+        };
+        let date = match published {
+            Some(ms) => html!(
+                <time class="content-date" datetime={el.date.clone()} title={absolute(ms)}>
+                    {relative(ms, now)}
+                </time>
+            ),
+            None => html!(<span class="content-date">{el.date.clone()}</span>),
+        };
+        html!(
+            <div class="content-feed">
+                {feed}
+                {date}
+            </div>
+        )
+    }
+
+    /// Loader and sentinel below the infinite stream.
+    fn get_stream_end(&self, ctx: &Context<Self>) -> Html {
+        if self.loading {
+            return html!(
+                <div class="content-loader">
+                    <i class="fas fa-spinner fa-spin"></i>{" loading"}
+                </div>
+            );
+        }
+        if let Some(error) = &self.load_more_error {
+            return html!(
+                <div class="content-error">
+                    <p class="error-message">{error}</p>
+                    <button type="button" class="content-error-button"
+                        onclick={ctx.link().callback(|_| NewsMessage::RetryLoadMore)}>{"retry"}</button>
+                </div>
+            );
+        }
+        if self.end_of_stream {
+            return html!(<div class="content-stream-end">{"You have reached the end"}</div>);
+        }
+        html!(<div class="content-sentinel" ref={self.sentinel.clone()}></div>)
+    }
+
     fn get_paging(&self, ctx: &Context<Self>) -> Html {
+        let end = self.start + self.page_len;
+        let last_page = self.end_of_stream || self.total.is_some_and(|total| end >= total);
         html! (
             <div class="content-paging center">
-                <button onclick={ctx.link().callback(|_| NewsMessage::Back)} type="button" class="content-paging-button"><i class="fas fa-angle-left"></i></button>
+                <button onclick={ctx.link().callback(|_| NewsMessage::Back)} type="button" class="content-paging-button"
+                    disabled={self.start == 0}><i class="fas fa-angle-left"></i></button>
                 <div class="content-paging-info">
-                    <p>{self.start}</p>
+                    <p>{(self.start + 1).min(end)}</p>
                     <p>{"    ...   "}</p>
-                    <p>{self.start + self.take}</p>
+                    <p>{end}</p>
+                    {
+                        match self.total {
+                            Some(total) => html!(<p class="content-paging-total">{format!("of {}", total)}</p>),
+                            None => html!(),
+                        }
+                    }
                 </div>
-                <button onclick={ctx.link().callback(|_| NewsMessage::Next)} type="button" class="content-paging-button"><i class="fas fa-angle-right"></i></button>
+                <button onclick={ctx.link().callback(|_| NewsMessage::Next)} type="button" class="content-paging-button"
+                    disabled={last_page}><i class="fas fa-angle-right"></i></button>
             </div>
         )
     }
@@ -151,7 +1218,7 @@ pub struct Props {
 #[function_component(SafeHtml)]
 pub fn safe_html(props: &Props) -> Html {
     let div: Element = gloo_utils::document().create_element("div").unwrap();
-    div.set_inner_html(&props.html.clone());
+    div.set_inner_html(&sanitize(&props.html));
     let res = div.get_elements_by_tag_name("img");
     let mut i = 0;
     while let Some(element) = res.item(i) {
@@ -160,3 +1227,7 @@ pub fn safe_html(props: &Props) -> Html {
     }
     Html::VRef(div.into())
 }
+
+mod read_observer;
+mod sentinel;
+mod shortcuts;
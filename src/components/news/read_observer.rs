@@ -0,0 +1,54 @@
+use js_sys::Array;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, IntersectionObserver, IntersectionObserverEntry};
+use yew::Callback;
+
+/// Reports the id of every `.content-element` that is scrolled past the top
+/// of the viewport.
+pub struct ReadObserver {
+    observer: IntersectionObserver,
+    _closure: Closure<dyn FnMut(Array, IntersectionObserver)>,
+}
+
+impl ReadObserver {
+    pub fn new(on_scrolled_past: Callback<String>) -> Option<Self> {
+        let closure = Closure::wrap(Box::new(move |entries: Array, _| {
+            for entry in entries.iter() {
+                let entry: IntersectionObserverEntry = entry.unchecked_into();
+                if entry.is_intersecting() || entry.bounding_client_rect().bottom() > 0.0 {
+                    continue;
+                }
+                if let Some(id) = entry.target().get_attribute("data-content-id") {
+                    on_scrolled_past.emit(id);
+                }
+            }
+        }) as Box<dyn FnMut(Array, IntersectionObserver)>);
+        let observer = IntersectionObserver::new(closure.as_ref().unchecked_ref()).ok()?;
+        Some(Self {
+            observer,
+            _closure: closure,
+        })
+    }
+
+    /// Starts watching the content elements currently in `list`, and only
+    /// those: other views on the page are not part of the stream.
+    pub fn observe_all(&self, list: Option<Element>) {
+        self.observer.disconnect();
+        let elements = match list.map(|list| list.query_selector_all(".content-element")) {
+            Some(Ok(elements)) => elements,
+            _ => return,
+        };
+        for i in 0..elements.length() {
+            if let Some(element) = elements.item(i).and_then(|n| n.dyn_into().ok()) {
+                self.observer.observe(&element);
+            }
+        }
+    }
+}
+
+impl Drop for ReadObserver {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
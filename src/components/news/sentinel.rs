@@ -0,0 +1,51 @@
+use js_sys::Array;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, IntersectionObserver, IntersectionObserverEntry, IntersectionObserverInit};
+use yew::Callback;
+
+/// How far below the viewport the sentinel starts loading the next page.
+const ROOT_MARGIN: &str = "0px 0px 600px 0px";
+
+/// Reports when the element after the last item comes close to the viewport.
+pub struct SentinelObserver {
+    observer: IntersectionObserver,
+    _closure: Closure<dyn FnMut(Array, IntersectionObserver)>,
+}
+
+impl SentinelObserver {
+    pub fn new(on_reached: Callback<()>) -> Option<Self> {
+        let closure = Closure::wrap(Box::new(move |entries: Array, _| {
+            let reached = entries.iter().any(|entry| {
+                let entry: IntersectionObserverEntry = entry.unchecked_into();
+                entry.is_intersecting()
+            });
+            if reached {
+                on_reached.emit(());
+            }
+        }) as Box<dyn FnMut(Array, IntersectionObserver)>);
+        let options = IntersectionObserverInit::new();
+        options.set_root_margin(ROOT_MARGIN);
+        let observer =
+            IntersectionObserver::new_with_options(closure.as_ref().unchecked_ref(), &options)
+                .ok()?;
+        Some(Self {
+            observer,
+            _closure: closure,
+        })
+    }
+
+    /// Watches `sentinel`, or nothing when there is no more to load.
+    pub fn observe(&self, sentinel: Option<Element>) {
+        self.observer.disconnect();
+        if let Some(sentinel) = sentinel {
+            self.observer.observe(&sentinel);
+        }
+    }
+}
+
+impl Drop for SentinelObserver {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
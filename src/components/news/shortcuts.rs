@@ -0,0 +1,88 @@
+use crate::store::{KeyAction, SettingsStore};
+use gloo_events::EventListener;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlElement, KeyboardEvent};
+use yew::prelude::*;
+
+/// Reports the keys pressed outside of form fields, without modifiers.
+pub fn listen(on_key: Callback<String>) -> EventListener {
+    EventListener::new(&gloo_utils::document(), "keydown", move |event| {
+        let event = match event.dyn_ref::<KeyboardEvent>() {
+            Some(event) => event,
+            None => return,
+        };
+        if event.ctrl_key() || event.alt_key() || event.meta_key() || event.repeat() {
+            return;
+        }
+        let key = event.key();
+        let target = event
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlElement>().ok());
+        if let Some(target) = target {
+            let tag = target.tag_name().to_ascii_uppercase();
+            if matches!(tag.as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+                || target.is_content_editable()
+            {
+                return;
+            }
+            // Enter already activates the focused link or button.
+            if key == "Enter" && matches!(tag.as_str(), "A" | "BUTTON") {
+                return;
+            }
+        }
+        on_key.emit(key);
+    })
+}
+
+#[derive(PartialEq, Properties)]
+pub struct HelpProps {
+    pub settings: Rc<SettingsStore>,
+    pub onclose: Callback<()>,
+}
+
+/// Overlay listing the shortcuts with the keys they are bound to.
+#[function_component(ShortcutsHelp)]
+pub fn shortcuts_help(props: &HelpProps) -> Html {
+    let close = props.onclose.reform(|_: MouseEvent| ());
+    html!(
+        <div class="shortcuts-overlay">
+            <div class="shortcuts-help" role="dialog" aria-label="Keyboard shortcuts">
+                <div class="shortcuts-help-header">
+                    <h4>{"Keyboard shortcuts"}</h4>
+                    <button class="button-icon" title="close" onclick={close}>
+                        <i class="fa-solid fa-xmark"></i>
+                    </button>
+                </div>
+                <table class="shortcuts-table">
+                    {
+                        for KeyAction::ALL.iter().map(|action| html!(
+                            <tr>
+                                <td class="shortcuts-keys">{bindings(&props.settings.bindings(*action))}</td>
+                                <td>{action.label()}</td>
+                            </tr>
+                        ))
+                    }
+                </table>
+            </div>
+        </div>
+    )
+}
+
+fn bindings(bindings: &[String]) -> Html {
+    if bindings.is_empty() {
+        return html!(<span class="shortcuts-unbound">{"not bound"}</span>);
+    }
+    bindings
+        .iter()
+        .enumerate()
+        .map(|(index, binding)| {
+            html!(
+                <>
+                    {if index > 0 { " or " } else { "" }}
+                    {for binding.split_whitespace().map(|key| html!(<kbd>{key}</kbd>))}
+                </>
+            )
+        })
+        .collect::<Html>()
+}
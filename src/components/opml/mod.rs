@@ -0,0 +1,293 @@
+use crate::api::{ApiClient, ApiError};
+use crate::components::link::validate::normalize_url;
+use crate::dto::{LinkCreatedDto, LinkDto};
+use crate::opml::{export_opml, parse_opml, OpmlFeed};
+use crate::store::{FolderStore, LinkStore, UserStore};
+use download::download;
+use log::error;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{EventTarget, HtmlInputElement};
+use yew::prelude::*;
+use yewdux::dispatch::{Dispatch, Dispatcher};
+use yewdux::prelude::BasicStore;
+
+pub enum OpmlMessage {
+    UserState(Rc<UserStore>),
+    LinkState(Rc<LinkStore>),
+    FolderState(Rc<FolderStore>),
+    FileRead(String),
+    FileFailed(String),
+    /// Subscriptions fetched before importing the feeds, to leave out duplicates.
+    Subscribed(Vec<OpmlFeed>, Result<Vec<LinkDto>, ApiError>),
+    /// Answer to creating the feed, with the folder to file it in.
+    Created(String, Option<String>, Result<LinkCreatedDto, ApiError>),
+    Finished,
+    Export,
+}
+
+/// Result of an import, filled in while the links are created.
+#[derive(Default)]
+pub struct ImportReport {
+    pub total: usize,
+    pub duplicates: usize,
+    pub created: Vec<String>,
+    pub failed: Vec<(String, String)>,
+    pub finished: bool,
+}
+
+pub struct OpmlComponent {
+    _dispatch: Dispatch<BasicStore<UserStore>>,
+    link_dispatch: Dispatch<BasicStore<LinkStore>>,
+    folder_dispatch: Dispatch<BasicStore<FolderStore>>,
+    state: Rc<UserStore>,
+    link_state: Rc<LinkStore>,
+    folders: Rc<FolderStore>,
+    report: Option<ImportReport>,
+    error: Option<String>,
+}
+
+impl Component for OpmlComponent {
+    type Message = OpmlMessage;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let dispatch = Dispatch::bridge_state(ctx.link().callback(OpmlMessage::UserState));
+        let link_dispatch = Dispatch::bridge_state(ctx.link().callback(OpmlMessage::LinkState));
+        let folder_dispatch = Dispatch::bridge_state(ctx.link().callback(OpmlMessage::FolderState));
+        Self {
+            _dispatch: dispatch,
+            link_dispatch,
+            folder_dispatch,
+            state: Default::default(),
+            link_state: Default::default(),
+            folders: Default::default(),
+            report: None,
+            error: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            OpmlMessage::UserState(state) => {
+                self.state = state;
+                false
+            }
+            OpmlMessage::LinkState(state) => {
+                self.link_state = state;
+                true
+            }
+            OpmlMessage::FolderState(state) => {
+                self.folders = state;
+                false
+            }
+            OpmlMessage::FileRead(text) => {
+                let feeds = match parse_opml(&text) {
+                    Ok(feeds) => feeds,
+                    Err(error) => {
+                        self.error = Some(error.to_string());
+                        return true;
+                    }
+                };
+                self.error = None;
+                self.report = None;
+                // The store may not hold the subscriptions yet, ask the backend.
+                let client = ApiClient::new(&self.state);
+                ctx.link().send_future(async move {
+                    OpmlMessage::Subscribed(feeds, client.get_links().await)
+                });
+                true
+            }
+            OpmlMessage::Subscribed(feeds, result) => {
+                let links = match result {
+                    Ok(links) => links,
+                    Err(error) => {
+                        self.error = Some(format!(
+                            "Could not check the existing subscriptions: {}",
+                            error
+                        ));
+                        return true;
+                    }
+                };
+                self.import(ctx, feeds, &links);
+                self.link_dispatch.reduce(move |s| s.links = links);
+                true
+            }
+            OpmlMessage::FileFailed(error) => {
+                self.error = Some(error);
+                true
+            }
+            OpmlMessage::Created(url, folder, result) => {
+                if let Some(report) = &mut self.report {
+                    match result {
+                        Ok(data) => {
+                            report.created.push(url);
+                            if let Some(folder) = folder {
+                                self.file(data.link_id.clone(), folder);
+                            }
+                            self.link_dispatch
+                                .reduce(move |s| s.links.push(data.into()));
+                        }
+                        Err(error) => report.failed.push((url, error.to_string())),
+                    }
+                }
+                true
+            }
+            OpmlMessage::Finished => {
+                if let Some(report) = &mut self.report {
+                    report.finished = true;
+                }
+                true
+            }
+            OpmlMessage::Export => {
+                let opml = export_opml(
+                    "RSS reader subscriptions",
+                    &self.link_state.links,
+                    &self.folders,
+                );
+                if let Err(error) = download("subscriptions.opml", "text/x-opml", &opml) {
+                    error!("export failed: {:?}", error);
+                    self.error = Some("Could not export the subscriptions".to_string());
+                    return true;
+                }
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link().clone();
+        let change = Callback::from(move |e: Event| {
+            let target: Option<EventTarget> = e.target();
+            let input = match target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok()) {
+                Some(input) => input,
+                None => return,
+            };
+            let file = match input.files().and_then(|files| files.get(0)) {
+                Some(file) => file,
+                None => return,
+            };
+            // Lets picking the same file again start another import.
+            input.set_value("");
+            let link = link.clone();
+            spawn_local(async move {
+                match JsFuture::from(file.text()).await {
+                    Ok(text) => link
+                        .send_message(OpmlMessage::FileRead(text.as_string().unwrap_or_default())),
+                    Err(_) => link.send_message(OpmlMessage::FileFailed(
+                        "Could not read the file".to_string(),
+                    )),
+                }
+            });
+        });
+        html!(
+            <div class="opml">
+                <h4 class="opml-header">{"Import / export"}</h4>
+                <div class="opml-actions">
+                    <label class="primary-button opml-button" for="opml-input">
+                        {"import OPML "}<i class="fa-solid fa-file-import"></i>
+                    </label>
+                    <input class="opml-input" id="opml-input" type="file"
+                        accept=".opml,.xml,text/xml,text/x-opml" onchange={change}/>
+                    <button class="primary-button opml-button" onclick={ctx.link().callback(|_| OpmlMessage::Export)}>
+                        {"export OPML "}<i class="fa-solid fa-file-export"></i>
+                    </button>
+                </div>
+                {
+                    match &self.error {
+                        Some(error) => html!(<p class="error-message">{error}</p>),
+                        None => html!(),
+                    }
+                }
+                {self.html_report()}
+            </div>
+        )
+    }
+}
+
+impl OpmlComponent {
+    /// Creates the feeds that are not subscribed yet, one after the other, and
+    /// files them in the folder named by their innermost category.
+    fn import(&mut self, ctx: &Context<Self>, feeds: Vec<OpmlFeed>, links: &[LinkDto]) {
+        let subscribed: HashMap<String, String> = links
+            .iter()
+            .map(|link| (normalize_url(&link.link), link.link_id.clone()))
+            .collect();
+        let mut seen = HashSet::new();
+        let total = feeds.len();
+        let mut pending = vec![];
+        for feed in feeds {
+            let url = normalize_url(&feed.xml_url);
+            let folder = feed.categories.last().cloned();
+            if let Some(link_id) = subscribed.get(&url) {
+                // Subscribed feeds keep the folder they are in already.
+                if let Some(folder) = folder.filter(|_| self.folders.folder_of(link_id).is_none()) {
+                    self.file(link_id.clone(), folder);
+                }
+            } else if seen.insert(url) {
+                pending.push((feed.xml_url, folder));
+            }
+        }
+        self.report = Some(ImportReport {
+            total,
+            duplicates: total - pending.len(),
+            ..Default::default()
+        });
+        let client = ApiClient::new(&self.state);
+        let link = ctx.link().clone();
+        spawn_local(async move {
+            for (url, folder) in pending {
+                let result = client.create_link(url.clone()).await;
+                let unauthorized = matches!(result, Err(ApiError::Unauthorized));
+                link.send_message(OpmlMessage::Created(url, folder, result));
+                if unauthorized {
+                    break;
+                }
+            }
+            link.send_message(OpmlMessage::Finished);
+        });
+    }
+
+    /// Files the link in the folder called `folder`, creating it when needed.
+    fn file(&self, link_id: String, folder: String) {
+        self.folder_dispatch.reduce(move |s| {
+            let folder_id = s.create(&folder);
+            s.move_link(&link_id, Some(&folder_id));
+        });
+    }
+
+    fn html_report(&self) -> Html {
+        let report = match &self.report {
+            Some(report) => report,
+            None => return html!(),
+        };
+        let done = report.created.len() + report.failed.len();
+        let pending = report.total - report.duplicates;
+        html!(
+            <div class="opml-report">
+                <p>
+                    {
+                        if report.finished {
+                            format!("Import finished: {} of {} feeds processed.", done, pending)
+                        } else {
+                            format!("Importing {} of {} feeds...", done, pending)
+                        }
+                    }
+                </p>
+                <p>{format!("{} added, {} already subscribed, {} failed",
+                    report.created.len(), report.duplicates, report.failed.len())}</p>
+                <ul class="opml-failures">
+                    {
+                        report.failed.iter().map(|(url, reason)| html!(
+                            <li class="error-message">{format!("{}: {}", url, reason)}</li>
+                        )).collect::<Html>()
+                    }
+                </ul>
+            </div>
+        )
+    }
+}
+
+pub mod download;
@@ -0,0 +1,281 @@
+use crate::store::PlayerStore;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlAudioElement, HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+use yewdux::dispatch::{Dispatch, Dispatcher};
+use yewdux::prelude::BasicStore;
+
+/// Playback speeds offered in the speed select.
+const RATES: [f64; 6] = [0.75, 1.0, 1.25, 1.5, 1.75, 2.0];
+/// The resume position is saved when playback moved this many seconds.
+const SAVE_EVERY_SECONDS: f64 = 5.0;
+
+pub enum PlayerMessage {
+    State(Rc<PlayerStore>),
+    Loaded,
+    TimeUpdate,
+    Played,
+    Paused,
+    Ended,
+    TogglePlay,
+    Skip(f64),
+    Seek(f64),
+    Rate(f64),
+    Close,
+}
+
+/// Mini-player kept at the bottom of every page, so playback survives
+/// route changes. News items only tell it what to play through `PlayerStore`.
+pub struct PlayerComponent {
+    dispatch: Dispatch<BasicStore<PlayerStore>>,
+    state: Rc<PlayerStore>,
+    audio: NodeRef,
+    current: f64,
+    duration: f64,
+    last_saved: f64,
+}
+
+impl Component for PlayerComponent {
+    type Message = PlayerMessage;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            dispatch: Dispatch::bridge_state(ctx.link().callback(PlayerMessage::State)),
+            state: Default::default(),
+            audio: NodeRef::default(),
+            current: 0.0,
+            duration: 0.0,
+            last_saved: 0.0,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            PlayerMessage::State(state) => {
+                let content_id = |state: &PlayerStore| {
+                    state
+                        .episode
+                        .as_ref()
+                        .map(|episode| episode.content_id.clone())
+                };
+                if content_id(&self.state) != content_id(&state) {
+                    self.save_position();
+                    self.current = 0.0;
+                    self.duration = 0.0;
+                }
+                self.state = state;
+                true
+            }
+            PlayerMessage::Loaded => {
+                if let (Some(audio), Some(episode)) = (self.audio(), &self.state.episode) {
+                    self.duration = audio.duration();
+                    if let Some(position) = self.state.position(&episode.content_id) {
+                        audio.set_current_time(position);
+                        self.current = position;
+                        self.last_saved = position;
+                    }
+                }
+                true
+            }
+            PlayerMessage::TimeUpdate => {
+                if let Some(audio) = self.audio() {
+                    self.current = audio.current_time();
+                    if (self.current - self.last_saved).abs() >= SAVE_EVERY_SECONDS {
+                        self.save_position();
+                    }
+                }
+                true
+            }
+            PlayerMessage::Played => {
+                if !self.state.playing {
+                    self.dispatch.reduce(|s| s.set_playing(true));
+                }
+                false
+            }
+            PlayerMessage::Paused => {
+                self.save_position();
+                if self.state.playing {
+                    self.dispatch.reduce(|s| s.set_playing(false));
+                }
+                false
+            }
+            PlayerMessage::Ended => {
+                if let Some(episode) = &self.state.episode {
+                    let content_id = episode.content_id.clone();
+                    self.dispatch.reduce(move |s| s.finish(&content_id));
+                }
+                false
+            }
+            PlayerMessage::TogglePlay => {
+                let playing = !self.state.playing;
+                self.dispatch.reduce(move |s| s.set_playing(playing));
+                false
+            }
+            PlayerMessage::Skip(seconds) => {
+                if let Some(audio) = self.audio() {
+                    let time = (audio.current_time() + seconds).max(0.0);
+                    audio.set_current_time(time);
+                }
+                false
+            }
+            PlayerMessage::Seek(time) => {
+                if let Some(audio) = self.audio() {
+                    audio.set_current_time(time);
+                }
+                false
+            }
+            PlayerMessage::Rate(rate) => {
+                self.dispatch.reduce(move |s| s.set_rate(rate));
+                false
+            }
+            PlayerMessage::Close => {
+                self.save_position();
+                self.dispatch.reduce(|s| s.close());
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let episode = match &self.state.episode {
+            Some(episode) => episode,
+            None => return html!(),
+        };
+        let seek = ctx.link().batch_callback(|e: InputEvent| {
+            let target: Option<EventTarget> = e.target();
+            let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+            input.and_then(|input| input.value().parse().ok().map(PlayerMessage::Seek))
+        });
+        let rate = ctx.link().batch_callback(|e: Event| {
+            let target: Option<EventTarget> = e.target();
+            let select = target.and_then(|t| t.dyn_into::<HtmlSelectElement>().ok());
+            select.and_then(|select| select.value().parse().ok().map(PlayerMessage::Rate))
+        });
+        let duration = if self.duration.is_finite() {
+            self.duration
+        } else {
+            0.0
+        };
+        html!(
+            <>
+            // Keeps the end of the page clear of the fixed bar.
+            <div class="player-spacer"></div>
+            <div class="player">
+                <audio ref={self.audio.clone()} src={episode.url.clone()} preload="metadata"
+                    onloadedmetadata={ctx.link().callback(|_| PlayerMessage::Loaded)}
+                    ontimeupdate={ctx.link().callback(|_| PlayerMessage::TimeUpdate)}
+                    onplay={ctx.link().callback(|_| PlayerMessage::Played)}
+                    onpause={ctx.link().callback(|_| PlayerMessage::Paused)}
+                    onended={ctx.link().callback(|_| PlayerMessage::Ended)}>
+                </audio>
+                {
+                    match &episode.image_url {
+                        Some(image_url) => html!(<img class="player-image" src={image_url.clone()} alt=""/>),
+                        None => html!(),
+                    }
+                }
+                <button class="button-icon player-button" title="back 15 seconds"
+                    onclick={ctx.link().callback(|_| PlayerMessage::Skip(-15.0))}>
+                    <i class="fa-solid fa-rotate-left"></i>
+                </button>
+                <button class="button-icon player-button player-toggle" title={if self.state.playing { "pause" } else { "play" }}
+                    onclick={ctx.link().callback(|_| PlayerMessage::TogglePlay)}>
+                    <i class={if self.state.playing { "fa-solid fa-pause" } else { "fa-solid fa-play" }}></i>
+                </button>
+                <button class="button-icon player-button" title="forward 30 seconds"
+                    onclick={ctx.link().callback(|_| PlayerMessage::Skip(30.0))}>
+                    <i class="fa-solid fa-rotate-right"></i>
+                </button>
+                <div class="player-track">
+                    <span class="player-title">{&episode.title}</span>
+                    <input class="player-seek" type="range" min="0" step="1"
+                        max={duration.to_string()} value={self.current.to_string()} oninput={seek}/>
+                </div>
+                <span class="player-time">{format!("{} / {}", format_time(self.current), format_time(duration))}</span>
+                <select class="player-rate" title="playback speed" onchange={rate}>
+                    {
+                        RATES.iter().map(|rate| html!(
+                            <option value={rate.to_string()} selected={*rate == self.state.rate}>
+                                {format!("{}x", rate)}
+                            </option>
+                        )).collect::<Html>()
+                    }
+                </select>
+                <button class="button-icon player-button" title="close"
+                    onclick={ctx.link().callback(|_| PlayerMessage::Close)}>
+                    <i class="fa-solid fa-xmark"></i>
+                </button>
+            </div>
+            </>
+        )
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        let audio = match self.audio() {
+            Some(audio) => audio,
+            None => return,
+        };
+        if audio.playback_rate() != self.state.rate {
+            audio.set_playback_rate(self.state.rate);
+        }
+        if self.state.playing && audio.paused() {
+            if let Err(error) = audio.play() {
+                log::error!("playback failed: {:?}", error);
+            }
+        } else if !self.state.playing && !audio.paused() {
+            let _ = audio.pause();
+        }
+    }
+}
+
+impl PlayerComponent {
+    fn audio(&self) -> Option<HtmlAudioElement> {
+        self.audio.cast::<HtmlAudioElement>()
+    }
+
+    fn save_position(&mut self) {
+        let episode = match &self.state.episode {
+            Some(episode) => episode,
+            None => return,
+        };
+        if self.current <= 0.0 || self.current == self.last_saved {
+            return;
+        }
+        self.last_saved = self.current;
+        let content_id = episode.content_id.clone();
+        let seconds = self.current;
+        self.dispatch
+            .reduce(move |s| s.save_position(&content_id, seconds));
+    }
+}
+
+/// Seconds as `m:ss`, or `h:mm:ss` from an hour on.
+pub fn format_time(seconds: f64) -> String {
+    let seconds = if seconds.is_finite() {
+        seconds.max(0.0) as u64
+    } else {
+        0
+    };
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// Reads an `itunes:duration`, given as seconds or `[hh:]mm:ss`.
+pub fn parse_duration(duration: &str) -> Option<f64> {
+    duration
+        .trim()
+        .split(':')
+        .try_fold(0.0, |total, part| {
+            part.trim()
+                .parse::<f64>()
+                .ok()
+                .map(|part| total * 60.0 + part)
+        })
+        .filter(|seconds| *seconds > 0.0)
+}
@@ -0,0 +1,403 @@
+use crate::api::{ApiClient, ApiError};
+use crate::components::feed_icon::FeedIcon;
+use crate::date::{absolute, parse_date, relative};
+use crate::dto::{ContentDto, LinkDto};
+use crate::router::{PageQuery, Route};
+use crate::sanitize::strip_tags;
+use crate::search::{highlight, snippet, SearchIndex, SearchQuery};
+use crate::store::{ContentCache, ReadStore, StarStore, StreamStore, UserStore};
+use js_sys::Date;
+use std::collections::HashSet;
+use std::rc::Rc;
+use yew::prelude::*;
+use yew_router::components::Link;
+use yew_router::history::Location;
+use yew_router::scope_ext::{HistoryHandle, RouterScopeExt};
+use yewdux::dispatch::{Dispatch, Dispatcher};
+use yewdux::prelude::BasicStore;
+
+/// Results shown at most.
+const MAX_RESULTS: usize = 100;
+/// Characters of the description shown around the first match.
+const SNIPPET_LENGTH: usize = 240;
+
+pub enum SearchMessage {
+    UserState(Rc<UserStore>),
+    ReadState(Rc<ReadStore>),
+    StarState(Rc<StarStore>),
+    CacheState(Rc<ContentCache>),
+    Navigated,
+    Links(Vec<LinkDto>),
+    Results(String, Result<Vec<ContentDto>, ApiError>),
+}
+
+/// Where the results came from.
+#[derive(PartialEq)]
+pub enum SearchSource {
+    Backend,
+    /// The index over this many items cached in the browser.
+    Local(usize),
+}
+
+/// Results for the `?q=` of the url, from the backend when it can search
+/// and from the items cached in this browser otherwise.
+pub struct SearchComponent {
+    _dispatch: Dispatch<BasicStore<UserStore>>,
+    _read_dispatch: Dispatch<BasicStore<ReadStore>>,
+    _star_dispatch: Dispatch<BasicStore<StarStore>>,
+    _cache_dispatch: Dispatch<BasicStore<ContentCache>>,
+    stream_dispatch: Dispatch<BasicStore<StreamStore>>,
+    state: Rc<UserStore>,
+    read_state: Rc<ReadStore>,
+    star_state: Rc<StarStore>,
+    cache: Rc<ContentCache>,
+    /// Searched items and the index over them, built on the first local search.
+    index: Option<(Vec<ContentDto>, SearchIndex)>,
+    links: Option<Vec<LinkDto>>,
+    text: String,
+    query: SearchQuery,
+    results: Option<Vec<ContentDto>>,
+    source: SearchSource,
+    /// False once the backend turned out not to support search.
+    backend: bool,
+    _history_listener: Option<HistoryHandle>,
+}
+
+impl Component for SearchComponent {
+    type Message = SearchMessage;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let dispatch = Dispatch::bridge_state(ctx.link().callback(SearchMessage::UserState));
+        let read_dispatch = Dispatch::bridge_state(ctx.link().callback(SearchMessage::ReadState));
+        let star_dispatch = Dispatch::bridge_state(ctx.link().callback(SearchMessage::StarState));
+        let cache_dispatch = Dispatch::bridge_state(ctx.link().callback(SearchMessage::CacheState));
+        let text = Self::url_text(ctx);
+        Self {
+            _dispatch: dispatch,
+            _read_dispatch: read_dispatch,
+            _star_dispatch: star_dispatch,
+            _cache_dispatch: cache_dispatch,
+            stream_dispatch: Dispatch::new(),
+            state: Default::default(),
+            read_state: Default::default(),
+            star_state: Default::default(),
+            cache: Default::default(),
+            index: None,
+            links: None,
+            query: SearchQuery::parse(&text),
+            text,
+            results: None,
+            source: SearchSource::Backend,
+            backend: true,
+            _history_listener: ctx
+                .link()
+                .add_history_listener(ctx.link().callback(|_| SearchMessage::Navigated)),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            SearchMessage::UserState(state) => {
+                self.state = state;
+                if self.state.token.is_empty() || self.state.token.eq("error") {
+                    return true;
+                }
+                self.load_links(ctx);
+                true
+            }
+            SearchMessage::ReadState(state) => {
+                self.read_state = state;
+                true
+            }
+            SearchMessage::StarState(state) => {
+                self.star_state = state;
+                self.index = None;
+                true
+            }
+            SearchMessage::CacheState(cache) => {
+                self.cache = cache;
+                self.index = None;
+                false
+            }
+            SearchMessage::Navigated => {
+                let text = Self::url_text(ctx);
+                if ctx.link().route::<Route>() != Some(Route::Search) || text == self.text {
+                    return false;
+                }
+                self.query = SearchQuery::parse(&text);
+                self.text = text;
+                self.search(ctx);
+                true
+            }
+            SearchMessage::Links(links) => {
+                self.links = Some(links);
+                self.search(ctx);
+                true
+            }
+            SearchMessage::Results(text, result) => {
+                if text != self.text {
+                    return false;
+                }
+                match result {
+                    Ok(items) => {
+                        let results = self.filter(items);
+                        self.show(results, SearchSource::Backend);
+                    }
+                    Err(error) => {
+                        if error.is_unsupported() {
+                            self.backend = false;
+                        }
+                        self.search_locally();
+                    }
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        html!(
+            <div class="container-content search">
+                <h3 class="content-header">{"Search"}</h3>
+                {self.html_status()}
+                {
+                    match &self.results {
+                        Some(results) => html!(
+                            <ul class="content-list">
+                                {for results.iter().map(|el| self.html_result(el))}
+                            </ul>
+                        ),
+                        None => html!(),
+                    }
+                }
+            </div>
+        )
+    }
+}
+
+impl SearchComponent {
+    fn url_text(ctx: &Context<Self>) -> String {
+        ctx.link()
+            .location()
+            .and_then(|location| location.query::<PageQuery>().ok())
+            .and_then(|query| query.q)
+            .unwrap_or_default()
+    }
+
+    fn load_links(&self, ctx: &Context<Self>) {
+        let client = ApiClient::new(&self.state);
+        ctx.link().send_future(async move {
+            match client.get_links().await {
+                Ok(data) => SearchMessage::Links(data),
+                Err(_) => SearchMessage::Links(vec![]),
+            }
+        });
+    }
+
+    /// Runs the query once the feeds are known, for the `feed:` operator.
+    fn search(&mut self, ctx: &Context<Self>) {
+        if self.links.is_none() {
+            return;
+        }
+        if self.query.is_empty() {
+            self.results = None;
+            return;
+        }
+        if !self.backend || !self.query.has_text() {
+            self.search_locally();
+            return;
+        }
+        let link_ids = match self.feed_ids() {
+            Some(link_ids) if link_ids.is_empty() => {
+                self.show(vec![], SearchSource::Backend);
+                return;
+            }
+            Some(link_ids) => link_ids.into_iter().collect(),
+            None => vec![],
+        };
+        let client = ApiClient::new(&self.state);
+        let text = self.text.clone();
+        let query = self.query.text();
+        let unread = self.query.unread;
+        ctx.link().send_future(async move {
+            let result = client.search(&query, &link_ids, unread).await;
+            SearchMessage::Results(text, result)
+        });
+    }
+
+    fn search_locally(&mut self) {
+        if self.index.is_none() {
+            let mut items = self.cache.items.clone();
+            let cached: HashSet<String> = items.iter().map(|el| el.content_id.clone()).collect();
+            items.extend(
+                self.star_state
+                    .items
+                    .iter()
+                    .filter(|item| !cached.contains(&item.content.content_id))
+                    .map(|item| item.content.clone()),
+            );
+            let bodies: Vec<String> = items.iter().map(plain_description).collect();
+            let index = SearchIndex::new(
+                items
+                    .iter()
+                    .zip(&bodies)
+                    .map(|(el, body)| (el.title.as_str(), body.as_str())),
+            );
+            self.index = Some((items, index));
+        }
+        let (items, index) = match &self.index {
+            Some(index) => index,
+            None => return,
+        };
+        let found = index
+            .search(&self.query)
+            .into_iter()
+            .map(|i| items[i].clone())
+            .collect();
+        let source = SearchSource::Local(index.len());
+        let results = self.filter(found);
+        self.show(results, source);
+    }
+
+    /// Feeds named by `feed:`, `None` when the query has no such operator.
+    fn feed_ids(&self) -> Option<HashSet<String>> {
+        if self.query.feeds.is_empty() {
+            return None;
+        }
+        let links = self.links.as_deref().unwrap_or_default();
+        let ids = links
+            .iter()
+            .filter(|link| {
+                let name = link.display_name().to_lowercase();
+                let url = link.link.to_lowercase();
+                self.query.feeds.iter().any(|feed| {
+                    link.link_id == *feed
+                        || name.contains(feed.as_str())
+                        || url.contains(feed.as_str())
+                })
+            })
+            .map(|link| link.link_id.clone())
+            .collect();
+        Some(ids)
+    }
+
+    /// Applies the operators to the found items.
+    fn filter(&self, mut items: Vec<ContentDto>) -> Vec<ContentDto> {
+        if let Some(link_ids) = self.feed_ids() {
+            items.retain(|el| link_ids.contains(&el.link_id));
+        }
+        if self.query.unread {
+            items.retain(|el| !self.read_state.is_read(el));
+        }
+        if self.query.starred {
+            items.retain(|el| self.star_state.is_starred(&el.content_id));
+        }
+        items.truncate(MAX_RESULTS);
+        items
+    }
+
+    /// Shows the results and lets the reader view step through them.
+    fn show(&mut self, results: Vec<ContentDto>, source: SearchSource) {
+        let items = results.clone();
+        let page = PageQuery {
+            q: Some(self.text.clone()),
+            ..PageQuery::default()
+        };
+        self.stream_dispatch
+            .reduce(move |s| s.set(items, "/search".to_string(), page));
+        self.results = Some(results);
+        self.source = source;
+    }
+
+    fn html_status(&self) -> Html {
+        if self.query.is_empty() {
+            return html!(
+                <p class="search-hint">
+                    {"Type words to find, "}<code>{"\"an exact phrase\""}</code>{", "}
+                    <code>{"feed:name"}</code>{", "}<code>{"is:unread"}</code>{" or "}
+                    <code>{"is:starred"}</code>{" into the search box."}
+                </p>
+            );
+        }
+        let count = match &self.results {
+            Some(results) => results.len(),
+            None => return html!(<p class="search-status">{"searching..."}</p>),
+        };
+        let found = match count {
+            0 => "No results".to_string(),
+            1 => "1 result".to_string(),
+            count if count >= MAX_RESULTS => format!("First {} results", count),
+            count => format!("{} results", count),
+        };
+        let source = match self.source {
+            SearchSource::Backend => String::new(),
+            SearchSource::Local(total) => {
+                format!(" among the {} articles cached in this browser", total)
+            }
+        };
+        html!(<p class="search-status">{format!("{} for \"{}\"{}", found, self.text, source)}</p>)
+    }
+
+    fn html_result(&self, el: &ContentDto) -> Html {
+        let feed = self
+            .links
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .find(|link| link.link_id == el.link_id);
+        let date = parse_date(&el.date).map(|ms| {
+            html!(
+                <time class="content-date" datetime={el.date.clone()} title={absolute(ms)}>
+                    {relative(ms, Date::now())}
+                </time>
+            )
+        });
+        let body = snippet(&plain_description(el), &self.query, SNIPPET_LENGTH);
+        let read = self.read_state.is_read(el);
+        html!(
+            <li class={classes!("content-element", "search-result", read.then_some("content-read"))}>
+                <div class="content-title">
+                    <Link<Route> to={Route::Article { id: el.content_id.clone() }}>
+                        {self.html_highlighted(&el.title)}
+                    </Link<Route>>
+                </div>
+                <div class="content-feed">
+                    {
+                        match feed {
+                            Some(link) => html!(
+                                <>
+                                    <FeedIcon url={link.icon()}/>
+                                    <span class="content-feed-name">{link.display_name()}</span>
+                                </>
+                            ),
+                            None => html!(),
+                        }
+                    }
+                    {date.unwrap_or_default()}
+                </div>
+                <p class="search-snippet">{self.html_highlighted(&body)}</p>
+            </li>
+        )
+    }
+
+    fn html_highlighted(&self, text: &str) -> Html {
+        highlight(text, &self.query)
+            .into_iter()
+            .map(|(part, matched)| {
+                if matched {
+                    html!(<mark>{part}</mark>)
+                } else {
+                    html!({ part })
+                }
+            })
+            .collect::<Html>()
+    }
+}
+
+fn plain_description(el: &ContentDto) -> String {
+    el.description
+        .as_deref()
+        .map(strip_tags)
+        .unwrap_or_default()
+}
@@ -0,0 +1,180 @@
+use crate::api::{ApiClient, ApiError};
+use crate::router::Route;
+use crate::store::{AuthState, UserStore};
+use gloo_timers::callback::Timeout;
+use js_sys::Date;
+use log::error;
+use std::rc::Rc;
+use yew::prelude::*;
+use yew_router::prelude::*;
+use yewdux::dispatch::{Dispatch, Dispatcher};
+use yewdux::prelude::BasicStore;
+
+/// How long before the token expires the user is warned.
+const WARN_BEFORE_MS: f64 = 5.0 * 60.0 * 1000.0;
+/// How long before the token expires a refresh is tried.
+const REFRESH_BEFORE_MS: f64 = 60.0 * 1000.0;
+/// Longest delay `setTimeout` accepts.
+const MAX_DELAY_MS: f64 = i32::MAX as f64;
+
+pub enum SessionMessage {
+    UserState(Rc<UserStore>),
+    /// Expiry was too far away to wait for, look at it again.
+    Reschedule,
+    Warn,
+    Refresh,
+    Refreshed(String),
+    RefreshFailed(ApiError),
+    Expire,
+}
+
+pub enum Stages {
+    Idle,
+    Warning(String),
+    Refreshing,
+    Failed(String),
+}
+
+/// Watches the token expiry, refreshes it shortly before it runs out and
+/// warns the user when that is not possible.
+pub struct SessionComponent {
+    dispatch: Dispatch<BasicStore<UserStore>>,
+    state: Rc<UserStore>,
+    stage: Stages,
+    timeouts: Vec<Timeout>,
+}
+
+impl Component for SessionComponent {
+    type Message = SessionMessage;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let dispatch = Dispatch::bridge_state(ctx.link().callback(SessionMessage::UserState));
+        Self {
+            dispatch,
+            state: Default::default(),
+            stage: Stages::Idle,
+            timeouts: vec![],
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            SessionMessage::UserState(state) => {
+                let expiry_changed = self.state.expires_at != state.expires_at;
+                self.state = state;
+                if expiry_changed {
+                    self.stage = Stages::Idle;
+                    self.schedule(ctx);
+                }
+                true
+            }
+            SessionMessage::Reschedule => {
+                self.schedule(ctx);
+                false
+            }
+            SessionMessage::Warn => {
+                self.stage = Stages::Warning(format!(
+                    "Your session expires in {} min.",
+                    self.minutes_left()
+                ));
+                true
+            }
+            SessionMessage::Refresh => {
+                self.stage = Stages::Refreshing;
+                let client = ApiClient::new(&self.state);
+                ctx.link().send_future(async move {
+                    match client.refresh_token().await {
+                        Ok(data) => SessionMessage::Refreshed(data.access_token),
+                        Err(error) => SessionMessage::RefreshFailed(error),
+                    }
+                });
+                true
+            }
+            SessionMessage::Refreshed(token) => {
+                self.stage = Stages::Idle;
+                let remember = self.state.remember;
+                self.dispatch.reduce(move |s| s.sign_in(token, remember));
+                true
+            }
+            SessionMessage::RefreshFailed(error) => {
+                error!("session refresh failed: {}", error);
+                self.stage = Stages::Failed(format!(
+                    "Your session expires in {} min and could not be extended.",
+                    self.minutes_left()
+                ));
+                true
+            }
+            SessionMessage::Expire => {
+                self.timeouts.clear();
+                self.stage = Stages::Idle;
+                ApiClient::new(&self.state).expire_session();
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        match &self.stage {
+            Stages::Idle => html!(),
+            Stages::Warning(message) => html!(
+                <div class="session-alert">
+                    {message}
+                    <button class="session-alert-button" onclick={ctx.link().callback(|_| SessionMessage::Refresh)}>
+                        {"stay signed in"}
+                    </button>
+                </div>
+            ),
+            Stages::Refreshing => html!(
+                <div class="session-alert">{"Refreshing your session..."}</div>
+            ),
+            Stages::Failed(message) => html!(
+                <div class="session-alert">
+                    {message}
+                    <button class="session-alert-button" onclick={ctx.link().callback(|_| SessionMessage::Refresh)}>
+                        {"retry"}
+                    </button>
+                    <Link <Route> classes={"session-alert-link"} to={Route::SignIn}>
+                        {"sign in again"}
+                    </Link<Route>>
+                </div>
+            ),
+        }
+    }
+}
+
+impl SessionComponent {
+    fn schedule(&mut self, ctx: &Context<Self>) {
+        self.timeouts.clear();
+        let expires_at = match (&self.state.auth_state, self.state.expires_at) {
+            (AuthState::Auth, Some(expires_at)) => expires_at,
+            _ => return,
+        };
+        let left = expires_at - Date::now();
+        if left > MAX_DELAY_MS {
+            // Wake up early enough that the warning is still due afterwards.
+            self.after(
+                ctx,
+                MAX_DELAY_MS - WARN_BEFORE_MS,
+                SessionMessage::Reschedule,
+            );
+            return;
+        }
+        self.after(ctx, left - WARN_BEFORE_MS, SessionMessage::Warn);
+        self.after(ctx, left - REFRESH_BEFORE_MS, SessionMessage::Refresh);
+        self.after(ctx, left, SessionMessage::Expire);
+    }
+
+    /// Sends `msg` after `ms`, right away when that is in the past.
+    fn after(&mut self, ctx: &Context<Self>, ms: f64, msg: SessionMessage) {
+        let link = ctx.link().clone();
+        let ms = ms.clamp(0.0, MAX_DELAY_MS) as u32;
+        self.timeouts
+            .push(Timeout::new(ms, move || link.send_message(msg)));
+    }
+
+    fn minutes_left(&self) -> f64 {
+        let left = self.state.expires_at.unwrap_or_default() - Date::now();
+        (left / 60_000.0).ceil().max(0.0)
+    }
+}
@@ -0,0 +1,97 @@
+use crate::store::{KeyAction, SettingsStore};
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlInputElement};
+use yew::prelude::*;
+use yewdux::dispatch::{Dispatch, Dispatcher};
+use yewdux::prelude::BasicStore;
+
+pub enum ShortcutsMessage {
+    SettingsState(Rc<SettingsStore>),
+    Input(KeyAction, String),
+    Reset,
+}
+
+/// Lets the reader remap the keyboard shortcuts of the news stream.
+pub struct ShortcutsComponent {
+    dispatch: Dispatch<BasicStore<SettingsStore>>,
+    settings: Rc<SettingsStore>,
+}
+
+impl Component for ShortcutsComponent {
+    type Message = ShortcutsMessage;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let dispatch = Dispatch::bridge_state(ctx.link().callback(ShortcutsMessage::SettingsState));
+        Self {
+            dispatch,
+            settings: Default::default(),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ShortcutsMessage::SettingsState(settings) => {
+                self.settings = settings;
+                true
+            }
+            ShortcutsMessage::Input(action, value) => {
+                let bindings = parse_bindings(&value);
+                self.dispatch
+                    .reduce(move |s| s.set_bindings(action, bindings));
+                false
+            }
+            ShortcutsMessage::Reset => {
+                self.dispatch.reduce(|s| s.reset_bindings());
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html!(
+            <div class="shortcuts">
+                <h4 class="shortcuts-header">{"Keyboard shortcuts"}</h4>
+                <p class="shortcuts-hint">
+                    {"Separate alternatives with commas and the keys of a sequence with spaces, e.g. "}
+                    <kbd>{"g s"}</kbd>{". Leave a field empty to turn the shortcut off."}
+                </p>
+                <table class="shortcuts-table">
+                    {for KeyAction::ALL.iter().map(|action| self.html_row(ctx, *action))}
+                </table>
+                <button class="primary-button" onclick={ctx.link().callback(|_| ShortcutsMessage::Reset)}>
+                    {"restore defaults"}
+                </button>
+            </div>
+        )
+    }
+}
+
+impl ShortcutsComponent {
+    fn html_row(&self, ctx: &Context<Self>, action: KeyAction) -> Html {
+        let change = ctx.link().batch_callback(move |e: Event| {
+            let target: Option<EventTarget> = e.target();
+            let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+            input.map(|input| ShortcutsMessage::Input(action, input.value()))
+        });
+        html!(
+            <tr>
+                <td>{action.label()}</td>
+                <td>
+                    <input class="shortcuts-input" type="text" aria-label={action.label()}
+                        value={self.settings.bindings(action).join(", ")} onchange={change}/>
+                </td>
+            </tr>
+        )
+    }
+}
+
+/// Reads `o, Enter` or `g  s` into bindings with single spaces between keys.
+fn parse_bindings(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|binding| binding.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|binding| !binding.is_empty())
+        .collect()
+}
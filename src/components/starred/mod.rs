@@ -0,0 +1,82 @@
+use crate::components::news::SafeHtml;
+use crate::store::StarStore;
+use std::rc::Rc;
+use yew::prelude::*;
+use yewdux::dispatch::{Dispatch, Dispatcher};
+use yewdux::prelude::BasicStore;
+
+pub enum StarredMessage {
+    StarState(Rc<StarStore>),
+    Unstar(String),
+}
+
+pub struct StarredComponent {
+    dispatch: Dispatch<BasicStore<StarStore>>,
+    state: Rc<StarStore>,
+}
+
+impl Component for StarredComponent {
+    type Message = StarredMessage;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let dispatch = Dispatch::bridge_state(ctx.link().callback(StarredMessage::StarState));
+        Self {
+            dispatch,
+            state: Default::default(),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            StarredMessage::StarState(state) => {
+                self.state = state;
+                true
+            }
+            StarredMessage::Unstar(content_id) => {
+                self.dispatch.reduce(move |s| s.remove(&content_id));
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html!(
+            <div class="container-content">
+                <h3 class="content-header">{"Starred"}</h3>
+                {
+                    if self.state.items.is_empty() {
+                        html!(<p class="content-empty">{"Nothing starred yet."}</p>)
+                    } else {
+                        html!(<ul class="content-list">{self.get_items(ctx)}</ul>)
+                    }
+                }
+            </div>
+        )
+    }
+}
+
+impl StarredComponent {
+    fn get_items(&self, ctx: &Context<Self>) -> Html {
+        self.state
+            .items
+            .iter()
+            .map(|item| {
+                let el = &item.content;
+                let content_id = el.content_id.clone();
+                html!(
+                    <li class="content-element">
+                        <div class="content-title">
+                            <a target="_blank" href={el.link_url.clone()}>{el.title.clone()}</a>
+                            <button class="button-icon content-star content-starred" title="unstar"
+                                onclick={ctx.link().callback(move |_| StarredMessage::Unstar(content_id.clone()))}>
+                                <i class="fa-solid fa-star"></i>
+                            </button>
+                        </div>
+                        <div class="content-desc"><SafeHtml html={el.description.clone().unwrap_or_default()}/></div>
+                    </li>
+                )
+            })
+            .collect::<Html>()
+    }
+}
@@ -0,0 +1,328 @@
+use js_sys::Date;
+use wasm_bindgen::JsValue;
+
+const MINUTE_MS: f64 = 60.0 * 1000.0;
+const HOUR_MS: f64 = 60.0 * MINUTE_MS;
+const DAY_MS: f64 = 24.0 * HOUR_MS;
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// Parses feed dates into ms since the unix epoch.
+///
+/// Accepts RFC 3339 / ISO 8601 (`2003-12-13T08:29:29-04:00`) and RFC 2822
+/// (`Tue, 10 Jun 2003 04:00:00 GMT`), plus the variants feeds get wrong:
+/// missing weekday, seconds or zone, full month names, two digit years,
+/// named US zones and offsets with a colon. Dates without a zone are UTC.
+pub fn parse_date(input: &str) -> Option<f64> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    if input.as_bytes()[0].is_ascii_digit() && input.get(4..5) == Some("-") {
+        parse_iso(input)
+    } else {
+        parse_rfc2822(input)
+    }
+}
+
+fn parse_iso(input: &str) -> Option<f64> {
+    let (date, rest) = input.split_at(input.find(['T', 't', ' ']).unwrap_or(input.len()));
+    let mut parts = date.split('-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next().map_or(Some(1), |month| month.parse().ok())?;
+    let day: u32 = parts.next().map_or(Some(1), |day| day.parse().ok())?;
+    let rest = rest.get(1..).unwrap_or("").trim();
+    let zone_index = rest
+        .find(['Z', 'z', '+', ' '])
+        .or_else(|| rest.rfind('-'))
+        .unwrap_or(rest.len());
+    let (time, zone) = rest.split_at(zone_index);
+    let seconds = parse_time(time)?;
+    let offset = parse_zone(zone.trim())?;
+    timestamp(year, month, day, seconds - offset)
+}
+
+fn parse_rfc2822(input: &str) -> Option<f64> {
+    let mut tokens = input
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .peekable();
+    // Weekday, when present.
+    if tokens
+        .peek()
+        .is_some_and(|token| token.chars().all(char::is_alphabetic) && month(token).is_none())
+    {
+        tokens.next();
+    }
+    let first = tokens.next()?;
+    // Most feeds write `10 Jun`, some `Jun 10`.
+    let (day, month) = match first.parse::<u32>() {
+        Ok(day) => (day, month(tokens.next()?)?),
+        Err(_) => (tokens.next()?.parse().ok()?, month(first)?),
+    };
+    let mut year: i64 = tokens.next()?.parse().ok()?;
+    if year < 100 {
+        year += if year < 50 { 2000 } else { 1900 };
+    }
+    let seconds = match tokens.peek() {
+        Some(token) if token.contains(':') => parse_time(tokens.next()?)?,
+        _ => 0,
+    };
+    let offset = match tokens.next() {
+        Some(zone) => parse_zone(zone).unwrap_or(0),
+        None => 0,
+    };
+    timestamp(year, month, day, seconds - offset)
+}
+
+/// `hh:mm[:ss[.fff]]` as seconds since midnight.
+fn parse_time(time: &str) -> Option<i64> {
+    if time.is_empty() {
+        return Some(0);
+    }
+    let mut parts = time.split(':');
+    let hours: i64 = parts.next()?.parse().ok()?;
+    let minutes: i64 = parts.next()?.parse().ok()?;
+    let seconds: f64 = parts
+        .next()
+        .map_or(Some(0.0), |seconds| seconds.parse().ok())?;
+    if hours > 24 || minutes > 59 || seconds >= 61.0 {
+        return None;
+    }
+    Some(hours * 3600 + minutes * 60 + seconds as i64)
+}
+
+/// Offset from UTC in seconds.
+fn parse_zone(zone: &str) -> Option<i64> {
+    let hours = match zone.to_ascii_uppercase().as_str() {
+        "" | "Z" | "UT" | "UTC" | "GMT" => 0,
+        "EDT" => -4,
+        "EST" | "CDT" => -5,
+        "CST" | "MDT" => -6,
+        "MST" | "PDT" => -7,
+        "PST" => -8,
+        _ => {
+            let sign = match zone.chars().next()? {
+                '+' => 1,
+                '-' => -1,
+                _ => return None,
+            };
+            let digits: String = zone[1..].chars().filter(char::is_ascii_digit).collect();
+            let (hours, minutes) = match digits.len() {
+                1 | 2 => (digits.parse::<i64>().ok()?, 0),
+                4 => (
+                    digits[..2].parse::<i64>().ok()?,
+                    digits[2..].parse::<i64>().ok()?,
+                ),
+                _ => return None,
+            };
+            return Some(sign * (hours * 3600 + minutes * 60));
+        }
+    };
+    Some(hours * 3600)
+}
+
+fn month(name: &str) -> Option<u32> {
+    let name = name.get(..3)?.to_ascii_lowercase();
+    MONTHS
+        .iter()
+        .position(|month| *month == name)
+        .map(|index| index as u32 + 1)
+}
+
+fn timestamp(year: i64, month: u32, day: u32, seconds: i64) -> Option<f64> {
+    // The range also keeps the arithmetic below from overflowing.
+    if !(1..=9999).contains(&year) || !(1..=12).contains(&month) {
+        return None;
+    }
+    if !(1..=days_in_month(year, month)).contains(&day) {
+        return None;
+    }
+    let days = days_from_civil(year, month, day);
+    Some((days * 86400 + seconds) as f64 * 1000.0)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Short age of a date, like `3h ago`.
+pub fn relative(ms: f64, now: f64) -> String {
+    let age = now - ms;
+    if age < MINUTE_MS {
+        return "just now".to_string();
+    }
+    let (value, unit) = if age < HOUR_MS {
+        (age / MINUTE_MS, "m")
+    } else if age < DAY_MS {
+        (age / HOUR_MS, "h")
+    } else if age < 7.0 * DAY_MS {
+        (age / DAY_MS, "d")
+    } else if age < 30.0 * DAY_MS {
+        (age / (7.0 * DAY_MS), "w")
+    } else if age < 365.0 * DAY_MS {
+        (age / (30.0 * DAY_MS), "mo")
+    } else {
+        (age / (365.0 * DAY_MS), "y")
+    };
+    format!("{}{} ago", value.floor(), unit)
+}
+
+/// Date and time in the user's locale and timezone.
+pub fn absolute(ms: f64) -> String {
+    Date::new(&JsValue::from_f64(ms))
+        .to_locale_string("default", &JsValue::UNDEFINED)
+        .into()
+}
+
+/// Section of the stream a date falls in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DateGroup {
+    Today,
+    Yesterday,
+    LastSevenDays,
+    Earlier,
+}
+
+impl DateGroup {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DateGroup::Today => "Today",
+            DateGroup::Yesterday => "Yesterday",
+            DateGroup::LastSevenDays => "Last 7 days",
+            DateGroup::Earlier => "Earlier",
+        }
+    }
+}
+
+/// Starts of today, yesterday and the last seven days in the local timezone.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DayBoundaries {
+    pub today: f64,
+    pub yesterday: f64,
+    pub week: f64,
+}
+
+impl DayBoundaries {
+    pub fn now() -> Self {
+        let now = Date::new_0();
+        let start_of = |days_ago: i32| {
+            Date::new_with_year_month_day(
+                now.get_full_year(),
+                now.get_month() as i32,
+                now.get_date() as i32 - days_ago,
+            )
+            .get_time()
+        };
+        Self {
+            today: start_of(0),
+            yesterday: start_of(1),
+            week: start_of(6),
+        }
+    }
+
+    pub fn group(&self, ms: f64) -> DateGroup {
+        if ms >= self.today {
+            DateGroup::Today
+        } else if ms >= self.yesterday {
+            DateGroup::Yesterday
+        } else if ms >= self.week {
+            DateGroup::LastSevenDays
+        } else {
+            DateGroup::Earlier
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2003-06-10T04:00:00Z
+    const JUN_10: f64 = 1055217600000.0;
+    /// 2003-12-13T12:29:29Z
+    const DEC_13: f64 = 1071318569000.0;
+
+    const CASES: &[(&str, Option<f64>)] = &[
+        ("Tue, 10 Jun 2003 04:00:00 GMT", Some(JUN_10)),
+        ("10 Jun 2003 04:00:00 GMT", Some(JUN_10)),
+        ("Jun 10 2003 04:00:00 GMT", Some(JUN_10)),
+        ("Tue, Jun 10, 2003 04:00:00 GMT", Some(JUN_10)),
+        ("Tuesday, 10 June 2003 04:00:00 GMT", Some(JUN_10)),
+        ("Tue, 10 Jun 03 04:00:00 GMT", Some(JUN_10)),
+        ("10 Jun 99 00:00 GMT", Some(928972800000.0)),
+        ("Tue, 10 Jun 2003 00:00:00 EDT", Some(JUN_10)),
+        ("Mon, 09 Jun 2003 20:00:00 PST", Some(JUN_10)),
+        ("Tue, 10 Jun 2003 09:30:00 +05:30", Some(JUN_10)),
+        ("Tue, 10 Jun 2003 06:00:00 +0200", Some(JUN_10)),
+        ("Tue, 10 Jun 2003 04:00:00", Some(JUN_10)),
+        ("Tue, 10 Jun 2003 04:00:00.250 GMT", Some(JUN_10)),
+        ("10 Jun 2003", Some(JUN_10 - 4.0 * HOUR_MS)),
+        ("2003-12-13T08:29:29-04:00", Some(DEC_13)),
+        ("2003-12-13T12:29:29Z", Some(DEC_13)),
+        ("2003-12-13t12:29:29z", Some(DEC_13)),
+        ("2003-12-13T12:29:29.123Z", Some(DEC_13)),
+        ("2003-12-13T14:59:29+02:30", Some(DEC_13)),
+        ("2003-12-13T17:59:29+0530", Some(DEC_13)),
+        ("2003-12-13 12:29:29", Some(DEC_13)),
+        ("2003-12-13", Some(1071273600000.0)),
+        ("1970-01-01T00:00:00Z", Some(0.0)),
+        ("2004-02-29", Some(1078012800000.0)),
+        ("29 Feb 2000", Some(951782400000.0)),
+        ("2003-04-30", Some(1051660800000.0)),
+        ("", None),
+        ("   ", None),
+        ("not a date", None),
+        ("Tue, 10 Foo 2003", None),
+        ("Tue, 32 Jun 2003", None),
+        ("10 Jun", None),
+        ("2003-13-01", None),
+        ("2003-12-32T00:00:00Z", None),
+        ("2003-02-29", None),
+        ("2003-02-31T00:00:00Z", None),
+        ("31 Apr 2003", None),
+        ("Sun, 31 Jun 2003 00:00:00 GMT", None),
+        ("1900-02-29", None),
+        ("2003-12-13T25:00:00Z", None),
+        ("2003-12-13T12:60:00Z", None),
+        ("2003-12-13T12:29:29+123456", None),
+        ("0000-01-01", None),
+        ("10000-01-01", None),
+        ("9223372036854775807-01-01", None),
+        ("10 Jun 9223372036854775807 00:00 GMT", None),
+    ];
+
+    #[test]
+    fn parses_feed_dates() {
+        for (input, expected) in CASES {
+            assert_eq!(parse_date(input), *expected, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn relative_ages() {
+        assert_eq!(relative(0.0, 30.0 * 1000.0), "just now");
+        assert_eq!(relative(0.0, 5.0 * MINUTE_MS), "5m ago");
+        assert_eq!(relative(0.0, 3.0 * HOUR_MS), "3h ago");
+        assert_eq!(relative(0.0, 2.0 * DAY_MS), "2d ago");
+        assert_eq!(relative(0.0, 400.0 * DAY_MS), "1y ago");
+    }
+}
@@ -6,6 +6,55 @@ pub struct ContentDto {
     pub link_url: String,
     pub title: String,
     pub description: Option<String>,
+    /// Full article, when the feed has more than the description.
+    #[serde(default)]
+    pub content: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
     pub date: String,
     pub link_id: String,
+    #[serde(default)]
+    pub read: bool,
+    /// Media file of podcast episodes.
+    #[serde(default)]
+    pub enclosure: Option<EnclosureDto>,
+    /// `itunes:duration`, as seconds or `[hh:]mm:ss`.
+    #[serde(default)]
+    pub duration: Option<String>,
+    /// `itunes:image` of the episode.
+    #[serde(default)]
+    pub image_url: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct EnclosureDto {
+    pub url: String,
+    #[serde(default, rename = "type")]
+    pub mime_type: Option<String>,
+    /// Size in bytes.
+    #[serde(default)]
+    pub length: Option<u64>,
+}
+
+impl ContentDto {
+    /// The enclosure, when it can be played as audio.
+    pub fn audio(&self) -> Option<&EnclosureDto> {
+        self.enclosure
+            .as_ref()
+            .filter(|enclosure| match &enclosure.mime_type {
+                Some(mime_type) => mime_type.starts_with("audio/"),
+                None => {
+                    let path = enclosure.url.split(['?', '#']).next().unwrap_or("");
+                    [".mp3", ".m4a", ".ogg", ".opus", ".aac", ".wav"]
+                        .iter()
+                        .any(|extension| path.to_ascii_lowercase().ends_with(extension))
+                }
+            })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct UnreadCountDto {
+    pub link_id: String,
+    pub count: u32,
 }
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Feed advertised by, or guessed for, a website.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct DiscoveredFeedDto {
+    pub url: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Mime type of the feed; `None` for paths the backend guessed without checking.
+    #[serde(default, rename = "type")]
+    pub kind: Option<String>,
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct FeedPreviewDto {
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub item_count: u32,
+    #[serde(default)]
+    pub items: Vec<FeedPreviewItemDto>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct FeedPreviewItemDto {
+    pub title: String,
+    #[serde(default)]
+    pub link_url: Option<String>,
+    #[serde(default)]
+    pub date: Option<String>,
+}
@@ -4,10 +4,108 @@ use serde::{Deserialize, Serialize};
 pub struct LinkDto {
     pub link_id: String,
     pub link: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub site_url: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub icon_url: Option<String>,
+    #[serde(default)]
+    pub last_fetched: Option<String>,
+    /// Display name chosen by the user.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Paused feeds are not fetched by the backend.
+    #[serde(default)]
+    pub paused: bool,
+}
+
+impl LinkDto {
+    /// Custom name or feed title, or the feed url while the backend has not
+    /// fetched it yet.
+    pub fn display_name(&self) -> String {
+        self.name
+            .iter()
+            .chain(self.title.iter())
+            .find(|title| !title.trim().is_empty())
+            .cloned()
+            .unwrap_or_else(|| self.link.clone())
+    }
+
+    /// Feed icon, falling back to the favicon of the site.
+    pub fn icon(&self) -> Option<String> {
+        self.icon_url.clone().or_else(|| {
+            let site_url = self.site_url.as_ref()?;
+            let (scheme, rest) = site_url.split_once("://")?;
+            let host = rest.split(['/', '?', '#']).next()?;
+            Some(format!("{}://{}/favicon.ico", scheme, host))
+        })
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct LinkCreatedDto {
     pub link_id: String,
     pub link: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub site_url: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub icon_url: Option<String>,
+    #[serde(default)]
+    pub last_fetched: Option<String>,
+}
+
+impl From<LinkCreatedDto> for LinkDto {
+    fn from(data: LinkCreatedDto) -> Self {
+        Self {
+            link_id: data.link_id,
+            link: data.link,
+            title: data.title,
+            site_url: data.site_url,
+            description: data.description,
+            icon_url: data.icon_url,
+            last_fetched: data.last_fetched,
+            name: None,
+            paused: false,
+        }
+    }
+}
+
+/// Changes to a subscription; fields left `None` are kept.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct LinkUpdateDto {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// An empty name resets the display name to the feed title.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paused: Option<bool>,
+}
+
+impl LinkUpdateDto {
+    pub fn is_empty(&self) -> bool {
+        self.link.is_none() && self.name.is_none() && self.paused.is_none()
+    }
+
+    /// The link as it will look once the update is applied.
+    pub fn apply(&self, link: &LinkDto) -> LinkDto {
+        let mut updated = link.clone();
+        if let Some(url) = &self.link {
+            updated.link = url.clone();
+        }
+        if let Some(name) = &self.name {
+            updated.name = Some(name.clone()).filter(|name| !name.trim().is_empty());
+        }
+        if let Some(paused) = self.paused {
+            updated.paused = paused;
+        }
+        updated
+    }
 }
@@ -1,10 +1,17 @@
 mod access_token_dto;
 mod content_dto;
+mod discovered_feed_dto;
+mod feed_preview_dto;
 mod link_dto;
 mod user_dto;
 
 pub use access_token_dto::AccessTokenDto;
 pub use content_dto::ContentDto;
+pub use content_dto::UnreadCountDto;
+pub use discovered_feed_dto::DiscoveredFeedDto;
+pub use feed_preview_dto::FeedPreviewDto;
+pub use feed_preview_dto::FeedPreviewItemDto;
 pub use link_dto::LinkCreatedDto;
 pub use link_dto::LinkDto;
+pub use link_dto::LinkUpdateDto;
 pub use user_dto::UserDto;
@@ -0,0 +1,97 @@
+use super::{non_empty, Enclosure, Feed, FeedFormat, FeedItem};
+use crate::xml::Element;
+
+pub fn parse(feed: &Element) -> Feed {
+    let authors = authors(feed);
+    Feed {
+        format: FeedFormat::Atom,
+        title: feed.child("title").and_then(text).unwrap_or_default(),
+        description: feed.child("subtitle").and_then(text),
+        site_url: link(feed, "alternate"),
+        icon_url: feed.child_text("icon").or_else(|| feed.child_text("logo")),
+        items: feed
+            .children_named("entry")
+            .map(|element| entry(element, &authors))
+            .collect(),
+        authors,
+    }
+}
+
+/// Entries without an author inherit the authors of the feed.
+fn entry(entry: &Element, feed_authors: &[String]) -> FeedItem {
+    let authors = authors(entry);
+    FeedItem {
+        id: entry.child_text("id"),
+        title: entry.child("title").and_then(text),
+        link: link(entry, "alternate"),
+        summary: entry.child("summary").and_then(text),
+        content: entry.child("content").and_then(text),
+        authors: if authors.is_empty() {
+            feed_authors.to_vec()
+        } else {
+            authors
+        },
+        published: entry
+            .child_text("published")
+            .or_else(|| entry.child_text("issued")),
+        updated: entry
+            .child_text("updated")
+            .or_else(|| entry.child_text("modified")),
+        categories: entry
+            .children_named("category")
+            .filter_map(|category| {
+                non_empty(
+                    category
+                        .attr("label")
+                        .or_else(|| category.attr("term"))
+                        .map(str::to_string),
+                )
+            })
+            .collect(),
+        enclosures: entry
+            .children_named("link")
+            .filter(|link| link.attr("rel") == Some("enclosure"))
+            .filter_map(|link| {
+                Some(Enclosure {
+                    url: non_empty(link.attr("href").map(str::to_string))?,
+                    mime_type: non_empty(link.attr("type").map(str::to_string)),
+                    length: link
+                        .attr("length")
+                        .and_then(|length| length.trim().parse().ok()),
+                })
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+/// `href` of the first link with the relation; links without `rel` are alternates.
+fn link(element: &Element, rel: &str) -> Option<String> {
+    element
+        .children_named("link")
+        .find(|link| link.attr("rel").unwrap_or("alternate") == rel)
+        .and_then(|link| non_empty(link.attr("href").map(str::to_string)))
+}
+
+fn authors(element: &Element) -> Vec<String> {
+    element
+        .children_named("author")
+        .filter_map(|author| {
+            author
+                .child_text("name")
+                .or_else(|| author.child_text("email"))
+        })
+        .collect()
+}
+
+/// Value of a text construct, as HTML for `html` and `xhtml` content.
+fn text(element: &Element) -> Option<String> {
+    let value = match element.attr("type") {
+        Some("xhtml") => match element.child("div") {
+            Some(div) => div.inner_xml(),
+            None => element.inner_xml(),
+        },
+        _ => element.text(),
+    };
+    non_empty(Some(value))
+}
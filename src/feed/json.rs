@@ -0,0 +1,124 @@
+use super::{non_empty, Enclosure, Feed, FeedError, FeedFormat, FeedItem};
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Deserialize)]
+struct JsonFeed {
+    #[serde(default)]
+    version: String,
+    #[serde(default)]
+    title: String,
+    home_page_url: Option<String>,
+    description: Option<String>,
+    icon: Option<String>,
+    favicon: Option<String>,
+    /// JSON Feed 1.0 had a single author.
+    author: Option<JsonAuthor>,
+    #[serde(default)]
+    authors: Vec<JsonAuthor>,
+    #[serde(default)]
+    items: Vec<JsonItem>,
+}
+
+#[derive(Deserialize)]
+struct JsonItem {
+    /// A string, although some feeds use numbers.
+    id: Option<Value>,
+    url: Option<String>,
+    external_url: Option<String>,
+    title: Option<String>,
+    content_html: Option<String>,
+    image: Option<String>,
+    content_text: Option<String>,
+    summary: Option<String>,
+    date_published: Option<String>,
+    date_modified: Option<String>,
+    author: Option<JsonAuthor>,
+    #[serde(default)]
+    authors: Vec<JsonAuthor>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    attachments: Vec<JsonAttachment>,
+}
+
+#[derive(Deserialize)]
+struct JsonAuthor {
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JsonAttachment {
+    url: String,
+    mime_type: Option<String>,
+    size_in_bytes: Option<u64>,
+}
+
+pub fn parse(input: &str) -> Result<Feed, FeedError> {
+    let feed: JsonFeed =
+        serde_json::from_str(input).map_err(|error| FeedError::Json(error.to_string()))?;
+    if !feed.version.starts_with("https://jsonfeed.org/version/") {
+        return Err(FeedError::Unsupported("json".to_string()));
+    }
+    let authors = names(feed.author, feed.authors);
+    Ok(Feed {
+        format: FeedFormat::JsonFeed,
+        title: feed.title,
+        description: non_empty(feed.description),
+        site_url: non_empty(feed.home_page_url),
+        icon_url: non_empty(feed.favicon).or_else(|| non_empty(feed.icon)),
+        items: feed
+            .items
+            .into_iter()
+            .map(|item| {
+                let item_authors = names(item.author, item.authors);
+                FeedItem {
+                    id: item.id.map(|id| match id {
+                        Value::String(id) => id,
+                        id => id.to_string(),
+                    }),
+                    title: non_empty(item.title),
+                    link: non_empty(item.url).or_else(|| non_empty(item.external_url)),
+                    summary: non_empty(item.summary),
+                    content: non_empty(item.content_html)
+                        .or_else(|| non_empty(item.content_text).map(|text| text_to_html(&text))),
+                    authors: if item_authors.is_empty() {
+                        authors.clone()
+                    } else {
+                        item_authors
+                    },
+                    published: non_empty(item.date_published),
+                    updated: non_empty(item.date_modified),
+                    categories: item.tags,
+                    enclosures: item
+                        .attachments
+                        .into_iter()
+                        .map(|attachment| Enclosure {
+                            url: attachment.url,
+                            mime_type: attachment.mime_type,
+                            length: attachment.size_in_bytes,
+                        })
+                        .collect(),
+                    image_url: non_empty(item.image),
+                    ..Default::default()
+                }
+            })
+            .collect(),
+        authors,
+    })
+}
+
+fn names(author: Option<JsonAuthor>, authors: Vec<JsonAuthor>) -> Vec<String> {
+    authors
+        .into_iter()
+        .chain(author)
+        .filter_map(|author| non_empty(author.name))
+        .collect()
+}
+
+/// Plain text content as paragraphs of HTML.
+fn text_to_html(text: &str) -> String {
+    text.split("\n\n")
+        .map(|paragraph| format!("<p>{}</p>", crate::xml::escape(paragraph.trim())))
+        .collect()
+}
@@ -0,0 +1,180 @@
+use crate::dto::{FeedPreviewDto, FeedPreviewItemDto};
+use crate::xml::{self, XmlError};
+use std::fmt;
+
+/// Feed in any of the supported formats.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Feed {
+    pub format: FeedFormat,
+    pub title: String,
+    pub description: Option<String>,
+    /// Website the feed belongs to.
+    pub site_url: Option<String>,
+    pub icon_url: Option<String>,
+    pub authors: Vec<String>,
+    pub items: Vec<FeedItem>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FeedFormat {
+    #[default]
+    Rss2,
+    /// RSS 1.0, also known as RDF Site Summary.
+    Rss1,
+    Atom,
+    JsonFeed,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FeedItem {
+    pub id: Option<String>,
+    pub title: Option<String>,
+    pub link: Option<String>,
+    /// Short description, HTML.
+    pub summary: Option<String>,
+    /// Full content, HTML.
+    pub content: Option<String>,
+    pub authors: Vec<String>,
+    /// Dates as written in the feed.
+    pub published: Option<String>,
+    pub updated: Option<String>,
+    pub categories: Vec<String>,
+    pub enclosures: Vec<Enclosure>,
+    /// `itunes:duration` as written in the feed.
+    pub duration: Option<String>,
+    /// `itunes:image` of a podcast episode.
+    pub image_url: Option<String>,
+}
+
+/// Media file attached to an item, e.g. a podcast episode.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Enclosure {
+    pub url: String,
+    pub mime_type: Option<String>,
+    /// Size in bytes.
+    pub length: Option<u64>,
+}
+
+impl FeedItem {
+    pub fn date(&self) -> Option<&str> {
+        self.published.as_deref().or(self.updated.as_deref())
+    }
+}
+
+impl Feed {
+    /// Makes relative links absolute against the website of the feed.
+    fn resolve_links(&mut self) {
+        let base = match &self.site_url {
+            Some(url) if url.contains("://") => url.clone(),
+            _ => return,
+        };
+        let resolve = |url: &mut String| *url = resolve_url(&base, url);
+        self.icon_url.iter_mut().for_each(resolve);
+        for item in &mut self.items {
+            item.link.iter_mut().for_each(resolve);
+            item.image_url.iter_mut().for_each(resolve);
+            for enclosure in &mut item.enclosures {
+                resolve(&mut enclosure.url);
+            }
+        }
+    }
+
+    pub fn to_preview(&self) -> FeedPreviewDto {
+        FeedPreviewDto {
+            title: self.title.clone(),
+            description: self.description.clone(),
+            item_count: self.items.len() as u32,
+            items: self
+                .items
+                .iter()
+                .map(|item| FeedPreviewItemDto {
+                    title: item.title.clone().unwrap_or_default(),
+                    link_url: item.link.clone(),
+                    date: item.date().map(str::to_string),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FeedError {
+    Xml(XmlError),
+    Json(String),
+    /// Well-formed document that is not a known feed format.
+    Unsupported(String),
+}
+
+impl fmt::Display for FeedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeedError::Xml(error) => write!(f, "{}", error),
+            FeedError::Json(error) => write!(f, "invalid json feed: {}", error),
+            FeedError::Unsupported(root) => write!(f, "not a feed: <{}>", root),
+        }
+    }
+}
+
+impl From<XmlError> for FeedError {
+    fn from(error: XmlError) -> Self {
+        FeedError::Xml(error)
+    }
+}
+
+/// Parses RSS 2.0, RSS 1.0, Atom 1.0 and JSON Feed 1.x documents.
+pub fn parse_feed(input: &str) -> Result<Feed, FeedError> {
+    let input = input.trim_start_matches('\u{feff}').trim_start();
+    if input.starts_with('{') {
+        return json::parse(input);
+    }
+    let root = xml::parse(input)?;
+    let mut feed = match root.local_name() {
+        "rss" => rss::parse_rss2(&root),
+        "RDF" => rss::parse_rss1(&root),
+        "feed" => atom::parse(&root),
+        other => return Err(FeedError::Unsupported(other.to_string())),
+    };
+    feed.resolve_links();
+    Ok(feed)
+}
+
+/// Resolves `href` found on the page at `base`.
+pub fn resolve_url(base: &str, href: &str) -> String {
+    if href.contains("://") {
+        return href.to_string();
+    }
+    let (scheme, rest) = base.split_once("://").unwrap_or(("https", base));
+    if let Some(href) = href.strip_prefix("//") {
+        return format!("{}://{}", scheme, href);
+    }
+    let (host, path) = match rest.find(['/', '?', '#']) {
+        Some(index) => rest.split_at(index),
+        None => (rest, "/"),
+    };
+    if href.starts_with('/') {
+        return format!("{}://{}{}", scheme, host, href);
+    }
+    let path = path.split(['?', '#']).next().unwrap_or("/");
+    let dir = &path[..path.rfind('/').map(|index| index + 1).unwrap_or(0)];
+    format!(
+        "{}://{}{}{}",
+        scheme,
+        host,
+        if dir.is_empty() { "/" } else { dir },
+        href
+    )
+}
+
+/// Drops empty values and surrounding whitespace.
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+mod atom;
+mod json;
+mod rss;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,121 @@
+use super::{non_empty, Enclosure, Feed, FeedFormat, FeedItem};
+use crate::xml::Element;
+
+pub fn parse_rss2(root: &Element) -> Feed {
+    // Some broken feeds put the channel elements right into <rss>.
+    let channel = root.child("channel").unwrap_or(root);
+    Feed {
+        format: FeedFormat::Rss2,
+        title: channel.child_text("title").unwrap_or_default(),
+        description: channel.child_text("description"),
+        site_url: channel_link(channel),
+        icon_url: channel
+            .child("image")
+            .and_then(|image| image.child_text("url")),
+        authors: authors(channel),
+        items: channel.children_named("item").map(item).collect(),
+    }
+}
+
+/// RSS 1.0 keeps the items next to the channel instead of inside it.
+pub fn parse_rss1(root: &Element) -> Feed {
+    let channel = root.child("channel");
+    Feed {
+        format: FeedFormat::Rss1,
+        title: channel
+            .and_then(|channel| channel.child_text("title"))
+            .unwrap_or_default(),
+        description: channel.and_then(|channel| channel.child_text("description")),
+        site_url: channel.and_then(channel_link),
+        icon_url: root
+            .child("image")
+            .and_then(|image| image.child_text("url")),
+        authors: channel.map(authors).unwrap_or_default(),
+        items: root.children_named("item").map(item).collect(),
+    }
+}
+
+/// The `link` of the channel, skipping `atom:link` self references.
+fn channel_link(channel: &Element) -> Option<String> {
+    channel
+        .elements()
+        .find(|element| element.name == "link")
+        .and_then(|link| non_empty(Some(link.text())))
+}
+
+fn item(item: &Element) -> FeedItem {
+    let link = item
+        .elements()
+        .find(|element| element.name == "link")
+        .and_then(|link| non_empty(Some(link.text())));
+    let guid = item.child("guid");
+    let permalink = guid
+        .filter(|guid| guid.attr("isPermaLink") != Some("false"))
+        .and_then(|guid| non_empty(Some(guid.text())))
+        .filter(|guid| guid.starts_with("http"));
+    FeedItem {
+        id: guid
+            .and_then(|guid| non_empty(Some(guid.text())))
+            .or_else(|| item.attr("rdf:about").map(str::to_string)),
+        title: item.child_text("title"),
+        link: link.or(permalink),
+        summary: item.child_text("description"),
+        content: item.child_text("content:encoded"),
+        authors: authors(item),
+        published: item
+            .child_text("pubDate")
+            .or_else(|| item.child_text("dc:date")),
+        updated: item.child_text("dc:modified"),
+        categories: item
+            .elements()
+            .filter(|element| element.is_named("category") || element.is_named("dc:subject"))
+            .filter_map(|category| non_empty(Some(category.text())))
+            .collect(),
+        enclosures: item
+            .children_named("enclosure")
+            .filter_map(|enclosure| {
+                Some(Enclosure {
+                    url: non_empty(enclosure.attr("url").map(str::to_string))?,
+                    mime_type: non_empty(enclosure.attr("type").map(str::to_string)),
+                    length: enclosure
+                        .attr("length")
+                        .and_then(|length| length.trim().parse().ok()),
+                })
+            })
+            .collect(),
+        duration: item.child_text("itunes:duration"),
+        image_url: item
+            .child("itunes:image")
+            .and_then(|image| non_empty(image.attr("href").map(str::to_string)))
+            .or_else(|| media_thumbnail(item)),
+    }
+}
+
+/// `media:thumbnail` of the item, also when nested in `media:content` or `media:group`.
+fn media_thumbnail(item: &Element) -> Option<String> {
+    item.child("media:thumbnail")
+        .or_else(|| {
+            item.children_named("media:content")
+                .chain(item.children_named("media:group"))
+                .find_map(|media| media.child("media:thumbnail"))
+        })
+        .and_then(|thumbnail| non_empty(thumbnail.attr("url").map(str::to_string)))
+}
+
+/// `author`, `dc:creator` and `itunes:author` of an item or channel.
+fn authors(element: &Element) -> Vec<String> {
+    let mut authors: Vec<String> = vec![];
+    for author in element.elements().filter(|element| {
+        element.is_named("author")
+            || element.is_named("managingEditor")
+            || element.is_named("dc:creator")
+            || element.is_named("itunes:author")
+    }) {
+        if let Some(name) = non_empty(Some(author.text())) {
+            if !authors.contains(&name) {
+                authors.push(name);
+            }
+        }
+    }
+    authors
+}
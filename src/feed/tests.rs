@@ -0,0 +1,204 @@
+use super::*;
+
+fn fixture(input: &str) -> Feed {
+    parse_feed(input).expect("fixture should parse")
+}
+
+#[test]
+fn rss2() {
+    let feed = fixture(include_str!("../../tests/fixtures/rss2.xml"));
+    assert_eq!(feed.format, FeedFormat::Rss2);
+    assert_eq!(feed.title, "Example & Co");
+    assert_eq!(feed.description.as_deref(), Some("News from example"));
+    assert_eq!(feed.site_url.as_deref(), Some("https://example.com/"));
+    assert_eq!(
+        feed.icon_url.as_deref(),
+        Some("https://example.com/icon.png")
+    );
+    assert_eq!(feed.items.len(), 2);
+
+    let first = &feed.items[0];
+    assert_eq!(first.id.as_deref(), Some("id-1"));
+    assert_eq!(first.title.as_deref(), Some("First post"));
+    assert_eq!(first.link.as_deref(), Some("https://example.com/1"));
+    assert_eq!(
+        first.summary.as_deref(),
+        Some("<p>Short <b>summary</b></p>")
+    );
+    assert_eq!(first.content.as_deref(), Some("<p>Full content</p>"));
+    assert_eq!(first.authors, vec!["Jane"]);
+    assert_eq!(first.date(), Some("Tue, 10 Jun 2003 04:00:00 GMT"));
+    assert_eq!(first.categories, vec!["Rust", "Web"]);
+    assert_eq!(
+        first.enclosures,
+        vec![Enclosure {
+            url: "https://example.com/ep1.mp3".to_string(),
+            mime_type: Some("audio/mpeg".to_string()),
+            length: Some(12345),
+        }]
+    );
+
+    let second = &feed.items[1];
+    assert_eq!(second.title, None);
+    assert_eq!(second.link.as_deref(), Some("https://example.com/2"));
+    assert_eq!(
+        second.summary.as_deref(),
+        Some("Only a description with <i>escaped</i> html")
+    );
+}
+
+#[test]
+fn rss1() {
+    let feed = fixture(include_str!("../../tests/fixtures/rss1.xml"));
+    assert_eq!(feed.format, FeedFormat::Rss1);
+    assert_eq!(feed.title, "XML.com");
+    assert_eq!(feed.site_url.as_deref(), Some("http://xml.com/pub"));
+    assert_eq!(feed.items.len(), 1);
+    let item = &feed.items[0];
+    assert_eq!(
+        item.title.as_deref(),
+        Some("Processing Inclusions with XSLT")
+    );
+    assert_eq!(
+        item.link.as_deref(),
+        Some("http://xml.com/pub/2000/08/09/xslt/xslt.html")
+    );
+    assert_eq!(item.date(), Some("2000-08-09T12:00:00Z"));
+    assert_eq!(item.categories, vec!["XSLT"]);
+}
+
+#[test]
+fn atom() {
+    let feed = fixture(include_str!("../../tests/fixtures/atom.xml"));
+    assert_eq!(feed.format, FeedFormat::Atom);
+    assert_eq!(feed.title, "dive into mark");
+    assert_eq!(feed.site_url.as_deref(), Some("http://example.org/"));
+    assert_eq!(
+        feed.icon_url.as_deref(),
+        Some("http://example.org/favicon.ico")
+    );
+    assert_eq!(feed.authors, vec!["Mark Pilgrim"]);
+    assert_eq!(feed.items.len(), 2);
+
+    let first = &feed.items[0];
+    assert_eq!(first.id.as_deref(), Some("tag:example.org,2003:3.2397"));
+    assert_eq!(
+        first.link.as_deref(),
+        Some("http://example.org/2005/04/02/atom")
+    );
+    assert_eq!(
+        first.published.as_deref(),
+        Some("2003-12-13T08:29:29-04:00")
+    );
+    assert_eq!(first.updated.as_deref(), Some("2005-07-31T12:29:29Z"));
+    let content = first.content.as_deref().unwrap();
+    assert!(content.contains("<i>[Update: The Atom draft is finished.]</i>"));
+    assert!(content.contains("a &amp; b"));
+    assert_eq!(first.enclosures.len(), 1);
+    assert_eq!(first.enclosures[0].length, Some(1337));
+
+    let second = &feed.items[1];
+    assert_eq!(second.link.as_deref(), Some("http://example.org/2"));
+    assert_eq!(second.authors, vec!["Someone"]);
+    assert_eq!(second.summary.as_deref(), Some("Plain summary"));
+}
+
+#[test]
+fn json_feed() {
+    let feed = fixture(include_str!("../../tests/fixtures/feed.json"));
+    assert_eq!(feed.format, FeedFormat::JsonFeed);
+    assert_eq!(feed.title, "My Example Feed");
+    assert_eq!(feed.site_url.as_deref(), Some("https://example.org/"));
+    assert_eq!(feed.authors, vec!["Alice"]);
+    assert_eq!(feed.items.len(), 2);
+    assert_eq!(feed.items[0].id.as_deref(), Some("2"));
+    assert_eq!(feed.items[0].categories, vec!["a"]);
+
+    let second = &feed.items[1];
+    assert_eq!(second.id.as_deref(), Some("1"));
+    assert_eq!(second.content.as_deref(), Some("<p>Hello, world!</p>"));
+    assert_eq!(second.authors, vec!["Bob"]);
+    assert_eq!(second.enclosures[0].url, "https://example.org/a.mp3");
+    assert_eq!(second.enclosures[0].length, Some(100));
+}
+
+#[test]
+fn malformed_rss2() {
+    let feed = fixture(include_str!("../../tests/fixtures/rss2-malformed.xml"));
+    // Entities XML does not declare are kept as written instead of failing.
+    assert_eq!(feed.title, "Caf&eacute; \u{a0}News &copy; &bogus; & more");
+    assert_eq!(
+        feed.icon_url.as_deref(),
+        Some("https://blog.example.com/icon.png")
+    );
+    assert_eq!(feed.items.len(), 3);
+
+    let first = &feed.items[0];
+    assert_eq!(
+        first.title.as_deref(),
+        Some("Tags <b>in</b> CDATA & ampersands")
+    );
+    assert_eq!(
+        first.link.as_deref(),
+        Some("https://blog.example.com/2024/first")
+    );
+    assert_eq!(
+        first.content.as_deref(),
+        Some(r#"<p>Full <a href="/x">content</a></p>"#)
+    );
+    assert_eq!(
+        first.image_url.as_deref(),
+        Some("https://cdn.example.com/thumb.jpg")
+    );
+
+    let second = &feed.items[1];
+    assert_eq!(
+        second.link.as_deref(),
+        Some("https://blog.example.com/news/second.html")
+    );
+    assert_eq!(second.enclosures[0].url, "https://cdn.example.com/ep.mp3");
+    assert_eq!(
+        second.image_url.as_deref(),
+        Some("https://blog.example.com/news/thumb2.jpg")
+    );
+}
+
+#[test]
+fn rss2_without_channel() {
+    let feed = fixture(include_str!("../../tests/fixtures/rss2-no-channel.xml"));
+    assert_eq!(feed.title, "Not inside a channel");
+    assert_eq!(feed.items.len(), 1);
+    assert_eq!(feed.items[0].title.as_deref(), Some("Orphan"));
+}
+
+#[test]
+fn rejects_other_documents() {
+    assert_eq!(
+        parse_feed("<html><body/></html>"),
+        Err(FeedError::Unsupported("html".to_string()))
+    );
+    assert!(matches!(parse_feed("{ not json"), Err(FeedError::Json(_))));
+    assert!(matches!(parse_feed(""), Err(FeedError::Xml(_))));
+}
+
+#[test]
+fn rejects_deep_nesting() {
+    let input = format!("<rss>{}</rss>", "<a>".repeat(100_000));
+    assert!(matches!(parse_feed(&input), Err(FeedError::Xml(_))));
+}
+
+#[test]
+fn resolves_relative_urls() {
+    let base = "https://example.com/blog/post?page=2";
+    assert_eq!(resolve_url(base, "http://other.org/"), "http://other.org/");
+    assert_eq!(resolve_url(base, "//cdn.org/a"), "https://cdn.org/a");
+    assert_eq!(resolve_url(base, "/feed"), "https://example.com/feed");
+    assert_eq!(
+        resolve_url(base, "rss.xml"),
+        "https://example.com/blog/rss.xml"
+    );
+    assert_eq!(
+        resolve_url("https://example.com", "a"),
+        "https://example.com/a"
+    );
+}
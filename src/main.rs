@@ -1,21 +1,33 @@
+use api::init_config;
+use components::player::PlayerComponent;
+use components::session::SessionComponent;
+use gloo_events::EventListener;
 use router::{switch, Route};
+use store::listen_session;
 use yew::prelude::*;
 use yew_router::prelude::*;
+use yewdux::dispatch::Dispatch;
 
 enum Msg {}
-struct RootComponent {}
+struct RootComponent {
+    _session_listener: EventListener,
+}
 impl Component for RootComponent {
     type Message = Msg;
     type Properties = ();
 
     fn create(_ctx: &Context<Self>) -> Self {
-        Self {}
+        Self {
+            _session_listener: listen_session(Dispatch::new()),
+        }
     }
 
     fn view(&self, _ctx: &Context<Self>) -> Html {
         html! {
             <BrowserRouter>
+                <SessionComponent />
                 <Switch<Route> render={Switch::render(switch)} />
+                <PlayerComponent />
             </BrowserRouter>
         }
     }
@@ -23,12 +35,19 @@ impl Component for RootComponent {
 
 fn main() {
     wasm_logger::init(wasm_logger::Config::default());
+    init_config();
     yew::start_app::<RootComponent>();
 }
 
 mod api;
 mod components;
+mod date;
 mod dto;
+mod feed;
+mod opml;
 mod pages;
 mod router;
+mod sanitize;
+mod search;
 mod store;
+mod xml;
@@ -0,0 +1,192 @@
+use crate::dto::LinkDto;
+use crate::store::FolderStore;
+use crate::xml::{self, escape, Element, XmlError};
+
+/// Feed found in an OPML file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpmlFeed {
+    pub xml_url: String,
+    pub title: Option<String>,
+    pub html_url: Option<String>,
+    /// Names of the enclosing category outlines, outermost first.
+    pub categories: Vec<String>,
+}
+
+/// Reads the feeds of an OPML 1.0 or 2.0 document, including nested categories.
+pub fn parse_opml(input: &str) -> Result<Vec<OpmlFeed>, XmlError> {
+    let root = xml::parse(input)?;
+    if !root.is_named("opml") {
+        return Err(XmlError("not an OPML document".to_string()));
+    }
+    let body = root
+        .child("body")
+        .ok_or_else(|| XmlError("OPML document has no body".to_string()))?;
+    let mut feeds = vec![];
+    collect_outlines(body, &mut vec![], &mut feeds);
+    Ok(feeds)
+}
+
+fn collect_outlines(parent: &Element, categories: &mut Vec<String>, feeds: &mut Vec<OpmlFeed>) {
+    for outline in parent.children_named("outline") {
+        let title = outline
+            .attr("title")
+            .or_else(|| outline.attr("text"))
+            .map(|title| title.trim().to_string())
+            .filter(|title| !title.is_empty());
+        match outline.attr("xmlUrl").map(str::trim) {
+            Some(xml_url) if !xml_url.is_empty() => feeds.push(OpmlFeed {
+                xml_url: xml_url.to_string(),
+                title,
+                html_url: outline.attr("htmlUrl").map(str::to_string),
+                categories: categories.clone(),
+            }),
+            _ => {
+                let pushed = title.is_some();
+                if let Some(title) = title {
+                    categories.push(title);
+                }
+                collect_outlines(outline, categories, feeds);
+                if pushed {
+                    categories.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Writes the links as an OPML 2.0 document, with a category outline per folder.
+pub fn export_opml(title: &str, links: &[LinkDto], folders: &FolderStore) -> String {
+    let mut opml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    opml.push_str("<opml version=\"2.0\">\n  <head>\n");
+    opml.push_str(&format!("    <title>{}</title>\n", escape(title)));
+    opml.push_str("  </head>\n  <body>\n");
+    let folder_of = |link: &LinkDto| {
+        folders
+            .folder_of(&link.link_id)
+            .and_then(|folder_id| folders.folder(folder_id))
+    };
+    for folder in &folders.folders {
+        let mut filed = links
+            .iter()
+            .filter(|link| folder_of(link).is_some_and(|of| of.folder_id == folder.folder_id))
+            .peekable();
+        if filed.peek().is_none() {
+            continue;
+        }
+        opml.push_str(&format!(
+            "    <outline text=\"{0}\" title=\"{0}\">\n",
+            escape(&folder.name)
+        ));
+        for link in filed {
+            opml.push_str(&feed_outline(link, "      "));
+        }
+        opml.push_str("    </outline>\n");
+    }
+    for link in links.iter().filter(|link| folder_of(link).is_none()) {
+        opml.push_str(&feed_outline(link, "    "));
+    }
+    opml.push_str("  </body>\n</opml>\n");
+    opml
+}
+
+fn feed_outline(link: &LinkDto, indent: &str) -> String {
+    let html_url = link
+        .site_url
+        .as_ref()
+        .map(|url| format!(" htmlUrl=\"{}\"", escape(url)))
+        .unwrap_or_default();
+    format!(
+        "{0}<outline type=\"rss\" text=\"{1}\" title=\"{1}\" xmlUrl=\"{2}\"{3}/>\n",
+        indent,
+        escape(&link.display_name()),
+        escape(&link.link),
+        html_url
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::Folder;
+
+    fn link(link_id: &str, url: &str, title: &str) -> LinkDto {
+        LinkDto {
+            link_id: link_id.to_string(),
+            link: url.to_string(),
+            title: Some(title.to_string()),
+            site_url: None,
+            description: None,
+            icon_url: None,
+            last_fetched: None,
+            name: None,
+            paused: false,
+        }
+    }
+
+    #[test]
+    fn folders_survive_export_and_import() {
+        let links = vec![
+            link("1", "https://a.example/feed", "A & co"),
+            link("2", "https://b.example/feed", "B"),
+            link("3", "https://c.example/feed", "C"),
+        ];
+        let folders = FolderStore {
+            folders: vec![
+                Folder {
+                    folder_id: "f1".to_string(),
+                    name: "News <daily>".to_string(),
+                },
+                Folder {
+                    folder_id: "f2".to_string(),
+                    name: "Empty".to_string(),
+                },
+            ],
+            assignments: [("1", "f1"), ("3", "gone")]
+                .into_iter()
+                .map(|(link_id, folder_id)| (link_id.to_string(), folder_id.to_string()))
+                .collect(),
+            collapsed: Default::default(),
+        };
+        let opml = export_opml("Subscriptions", &links, &folders);
+        assert!(!opml.contains("Empty"));
+
+        let feeds = parse_opml(&opml).unwrap();
+        let summary: Vec<(&str, Option<&str>, Vec<&str>)> = feeds
+            .iter()
+            .map(|feed| {
+                (
+                    feed.xml_url.as_str(),
+                    feed.title.as_deref(),
+                    feed.categories.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "https://a.example/feed",
+                    Some("A & co"),
+                    vec!["News <daily>"]
+                ),
+                ("https://b.example/feed", Some("B"), vec![]),
+                ("https://c.example/feed", Some("C"), vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_categories() {
+        let opml = r#"<opml version="1.0"><body>
+            <outline text="Tech"><outline title="Rust">
+                <outline text="This week" xmlUrl=" https://this-week-in-rust.org/rss.xml "/>
+            </outline></outline>
+            <outline text="No url"/>
+        </body></opml>"#;
+        let feeds = parse_opml(opml).unwrap();
+        assert_eq!(feeds.len(), 1);
+        assert_eq!(feeds[0].xml_url, "https://this-week-in-rust.org/rss.xml");
+        assert_eq!(feeds[0].categories, vec!["Tech", "Rust"]);
+        assert!(parse_opml("<rss/>").is_err());
+    }
+}
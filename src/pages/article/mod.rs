@@ -0,0 +1,76 @@
+use crate::components::article::ArticleComponent;
+use crate::components::nav::NavComponent;
+use crate::router::Route;
+use crate::store::UserStore;
+use std::rc::Rc;
+use yew::{html, Component, Context, Html, Properties};
+use yew_router::prelude::*;
+use yewdux::dispatch::Dispatch;
+use yewdux::prelude::BasicStore;
+
+pub enum Stages {
+    Auth,
+    UnAuth,
+}
+
+#[derive(PartialEq, Properties)]
+pub struct ArticleProps {
+    pub id: String,
+}
+
+pub enum ArticleMessage {
+    UserState(Rc<UserStore>),
+}
+
+pub struct ArticlePage {
+    _dispatch: Dispatch<BasicStore<UserStore>>,
+    state: Rc<UserStore>,
+    stage: Stages,
+}
+
+impl Component for ArticlePage {
+    type Message = ArticleMessage;
+    type Properties = ArticleProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let dispatch = Dispatch::bridge_state(ctx.link().callback(ArticleMessage::UserState));
+
+        Self {
+            _dispatch: dispatch,
+            state: Rc::new(Default::default()),
+            stage: Stages::Auth,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ArticleMessage::UserState(state) => {
+                self.state = state;
+                if self.state.token.is_empty() || self.state.token.eq("error") {
+                    self.stage = Stages::UnAuth
+                } else {
+                    self.stage = Stages::Auth
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        match self.stage {
+            Stages::Auth => {
+                html! (
+                    <main>
+                        <NavComponent/>
+                        <ArticleComponent id={ctx.props().id.clone()} />
+                    </main>
+                )
+            }
+            Stages::UnAuth => {
+                html!(
+                    <Redirect<Route> to={Route::Home}/>
+                )
+            }
+        }
+    }
+}
@@ -1,23 +1,29 @@
-use yew::{html, Component, Context, Html};
+use yew::{html, Component, Context, Html, Properties};
 
 use crate::components::news::NewsComponent;
 
+#[derive(PartialEq, Properties)]
+pub struct ContentProps {
+    pub feed_id: Option<String>,
+    pub folder_id: Option<String>,
+}
+
 pub enum ContentMessage {}
 
 pub struct ContentPage {}
 
 impl Component for ContentPage {
     type Message = ContentMessage;
-    type Properties = ();
+    type Properties = ContentProps;
 
     fn create(_ctx: &Context<Self>) -> Self {
         Self {}
     }
 
-    fn view(&self, _ctx: &Context<Self>) -> Html {
+    fn view(&self, ctx: &Context<Self>) -> Html {
         html! (
             <div>
-                <NewsComponent />
+                <NewsComponent feed_id={ctx.props().feed_id.clone()} folder_id={ctx.props().folder_id.clone()} />
             </div>
         )
     }
@@ -3,7 +3,7 @@ use crate::components::nav::NavComponent;
 use crate::store::{AuthState, UserStore};
 use content::ContentPage;
 use std::rc::Rc;
-use yew::{html, Component, Context, Html};
+use yew::{html, Component, Context, Html, Properties};
 use yewdux::dispatch::Dispatch;
 use yewdux::prelude::BasicStore;
 
@@ -12,6 +12,16 @@ pub enum Stages {
     UnAuth,
 }
 
+#[derive(PartialEq, Properties)]
+pub struct HomeProps {
+    /// Only show the stream of this feed.
+    #[prop_or_default]
+    pub feed_id: Option<String>,
+    /// Only show the merged stream of the feeds in this folder.
+    #[prop_or_default]
+    pub folder_id: Option<String>,
+}
+
 pub enum HomeMessage {
     UserState(Rc<UserStore>),
 }
@@ -22,7 +32,7 @@ pub struct HomePage {
 }
 impl Component for HomePage {
     type Message = HomeMessage;
-    type Properties = ();
+    type Properties = HomeProps;
 
     fn create(ctx: &Context<Self>) -> Self {
         let dispatch = Dispatch::bridge_state(ctx.link().callback(HomeMessage::UserState));
@@ -47,13 +57,13 @@ impl Component for HomePage {
         }
     }
 
-    fn view(&self, _ctx: &Context<Self>) -> Html {
+    fn view(&self, ctx: &Context<Self>) -> Html {
         match self.state.auth_state {
             AuthState::Auth => {
                 html! (
                     <main>
                         <NavComponent/>
-                        <ContentPage />
+                        <ContentPage feed_id={ctx.props().feed_id.clone()} folder_id={ctx.props().folder_id.clone()} />
                     </main>
                 )
             }
@@ -1,4 +1,7 @@
+pub mod article;
 pub mod home;
+pub mod search;
 pub mod settings;
 pub mod sign_in;
 pub mod sign_up;
+pub mod starred;
@@ -1,5 +1,7 @@
 use crate::components::link::LinkComponent;
 use crate::components::nav::NavComponent;
+use crate::components::opml::OpmlComponent;
+use crate::components::shortcuts::ShortcutsComponent;
 use crate::router::Route;
 use crate::store::UserStore;
 use std::rc::Rc;
@@ -58,6 +60,8 @@ impl Component for SettingsPage {
                         <div class="settings-container">
                             <NavComponent/>
                             <LinkComponent />
+                            <OpmlComponent />
+                            <ShortcutsComponent />
                         </div>
                     </main>
                 )
@@ -1,9 +1,8 @@
-use crate::api::sign_in_api;
+use crate::api::{sign_in_api, ApiError};
 use crate::components::nav::NavComponent;
 use crate::router::Route;
-use crate::store::{AuthState, UserStore};
+use crate::store::UserStore;
 use log::info;
-use reqwasm::Error;
 use std::rc::Rc;
 use wasm_bindgen::JsCast;
 use web_sys::{EventTarget, FocusEvent, HtmlInputElement};
@@ -17,8 +16,9 @@ pub enum SignInMessage {
     Success(String),
     InputUsername(String),
     InputPassword(String),
+    InputRemember(bool),
     UserState(Rc<UserStore>),
-    Error(Error),
+    Error(ApiError),
 }
 
 pub enum Stages {
@@ -30,6 +30,7 @@ pub enum Stages {
 pub struct SignInPage {
     username: String,
     password: String,
+    remember: bool,
     dispatch: Dispatch<BasicStore<UserStore>>,
     state: Rc<UserStore>,
     stage: Stages,
@@ -44,6 +45,7 @@ impl Component for SignInPage {
         Self {
             username: "".to_string(),
             password: "".to_string(),
+            remember: true,
             dispatch,
             state: Default::default(),
             stage: Stages::SignUp,
@@ -60,6 +62,10 @@ impl Component for SignInPage {
                 self.password = password;
                 true
             }
+            SignInMessage::InputRemember(remember) => {
+                self.remember = remember;
+                true
+            }
             SignInMessage::SignIn => {
                 let username = self.username.clone();
                 let password = self.password.clone();
@@ -73,8 +79,8 @@ impl Component for SignInPage {
                 false
             }
             SignInMessage::Success(token) => {
-                self.dispatch.reduce(|s| s.token = token);
-                self.dispatch.reduce(|s| s.auth_state = AuthState::Auth);
+                let remember = self.remember;
+                self.dispatch.reduce(move |s| s.sign_in(token, remember));
                 self.stage = Stages::Success;
                 true
             }
@@ -88,10 +94,11 @@ impl Component for SignInPage {
                     self.username.clone(),
                     self.password.clone()
                 );
-                match error {
-                    Error::JsError(error) => self.stage = Stages::Error(error.name),
-                    Error::SerdeError(error) => self.stage = Stages::Error(error.to_string()),
-                }
+                // A 401 from the sign in itself means the credentials are wrong.
+                self.stage = Stages::Error(match error {
+                    ApiError::Unauthorized => "Wrong credentials".to_string(),
+                    error => error.to_string(),
+                });
                 true
             }
         }
@@ -107,24 +114,27 @@ impl Component for SignInPage {
                         <div class="center form-container">
                             <form class="form form-auth" onsubmit={change}>
                                 {self.get_header()}
+                                {self.get_notice()}
                                 {self.html_input_username(ctx)}
                                 {self.html_input_password(ctx)}
+                                {self.html_input_remember(ctx)}
                                 {self.html_button_login(ctx)}
                             </form>
                         </div>
                     </main>
                 )
             }
-            Stages::Error(_error) => {
+            Stages::Error(error) => {
                 html!(
                     <main>
                         <NavComponent/>
                         <div class="center form-container">
                             <form class="form form-auth" onsubmit={change}>
                                 {self.get_header()}
-                                {self.get_error_message()}
+                                {self.get_error_message(error)}
                                 {self.html_input_username(ctx)}
                                 {self.html_input_password(ctx)}
+                                {self.html_input_remember(ctx)}
                                 {self.html_button_login(ctx)}
                             </form>
                         </div>
@@ -191,15 +201,44 @@ impl SignInPage {
         }
     }
 
+    fn html_input_remember(&self, ctx: &Context<Self>) -> Html {
+        let change = ctx.link().batch_callback(|e: Event| {
+            let target: Option<EventTarget> = e.target();
+            let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+            input.map(|input| SignInMessage::InputRemember(input.checked()))
+        });
+        html! {
+            <div class="form-element row-direction center">
+                <input class="primary-checkbox" onchange={change}
+                    id="remember-input"
+                    type="checkbox"
+                    checked={self.remember}
+                />
+                <label class="primary-input-label" for="remember-input">
+                    { "Remember me" }
+                </label>
+            </div>
+        }
+    }
+
     fn get_header(&self) -> Html {
         html!(
             <h3 class="form-element column-direction center form-header">{"Please, sign in"}</h3>
         )
     }
 
-    fn get_error_message(&self) -> Html {
+    fn get_notice(&self) -> Html {
+        match &self.state.notice {
+            Some(notice) => html!(
+                <p class="form-element column-direction center form-notice">{notice}</p>
+            ),
+            None => html!(),
+        }
+    }
+
+    fn get_error_message(&self, error: &str) -> Html {
         html!(
-            <h3 class="form-element column-direction center form-header error-message">{"Wrong credentials"}</h3>
+            <h3 class="form-element column-direction center form-header error-message">{error}</h3>
         )
     }
 }
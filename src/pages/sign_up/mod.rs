@@ -1,4 +1,4 @@
-use crate::api::sign_up_api;
+use crate::api::{sign_up_api, ApiError};
 use crate::components::nav::NavComponent;
 use crate::router::Route;
 use crate::store::UserStore;
@@ -18,11 +18,13 @@ pub enum SignUpMessage {
     InputPassword(String),
     InputPasswordRepeat(String),
     UserState(Rc<UserStore>),
+    Error(ApiError),
 }
 
 pub enum Stages {
     SignUp,
     Success,
+    Error(String),
 }
 
 pub struct SignUpPage {
@@ -75,7 +77,7 @@ impl Component for SignUpPage {
                 ctx.link().send_future(async {
                     match sign_up_api(username, password).await {
                         Ok(data) => SignUpMessage::Success(data.user_id),
-                        Err(_) => SignUpMessage::Success("error".to_string()),
+                        Err(error) => SignUpMessage::Error(error),
                     }
                 });
                 false
@@ -90,12 +92,16 @@ impl Component for SignUpPage {
                 self.state = state;
                 true
             }
+            SignUpMessage::Error(error) => {
+                self.stage = Stages::Error(error.to_string());
+                true
+            }
         }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let change = |e: FocusEvent| e.prevent_default();
-        match self.stage {
+        match &self.stage {
             Stages::SignUp => {
                 html!(
                     <main>
@@ -112,6 +118,23 @@ impl Component for SignUpPage {
                     </main>
                 )
             }
+            Stages::Error(error) => {
+                html!(
+                    <main>
+                        <NavComponent/>
+                        <div class="form-container center">
+                            <form class="form form-auth" onsubmit={change}>
+                                {self.get_header()}
+                                {self.get_error_message(error)}
+                                {self.html_input_username(ctx)}
+                                {self.html_input_password(ctx)}
+                                {self.html_input_repeat_password(ctx)}
+                                {self.html_button_signup(ctx)}
+                            </form>
+                        </div>
+                    </main>
+                )
+            }
             Stages::Success => {
                 html!(<Redirect<Route> to={Route::Home}/>)
             }
@@ -192,4 +215,10 @@ impl SignUpPage {
             <h3 class="form-element column-direction center">{"Please, sign up"}</h3>
         )
     }
+
+    fn get_error_message(&self, error: &str) -> Html {
+        html!(
+            <h3 class="form-element column-direction center form-header error-message">{error}</h3>
+        )
+    }
 }
@@ -1,5 +1,7 @@
+mod query;
 mod route;
 mod switch;
 
+pub use query::PageQuery;
 pub use route::Route;
 pub use switch::switch;
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// Position in a paged stream, kept in the url as `?start=30&take=15`,
+/// and the text of a search as `?q=`.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct PageQuery {
+    #[serde(default)]
+    pub start: u32,
+    /// Page size, the one from the settings when missing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub take: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+}
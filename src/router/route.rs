@@ -10,6 +10,16 @@ pub enum Route {
     SignUp,
     #[at("/settings")]
     Settings,
+    #[at("/starred")]
+    Starred,
+    #[at("/search")]
+    Search,
+    #[at("/feed/:id")]
+    Feed { id: String },
+    #[at("/folder/:id")]
+    Folder { id: String },
+    #[at("/article/:id")]
+    Article { id: String },
     #[not_found]
     #[at("/404")]
     NotFound,
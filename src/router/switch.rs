@@ -1,5 +1,6 @@
 use crate::pages::{
-    home::HomePage, settings::SettingsPage, sign_in::SignInPage, sign_up::SignUpPage,
+    article::ArticlePage, home::HomePage, search::SearchPage, settings::SettingsPage,
+    sign_in::SignInPage, sign_up::SignUpPage, starred::StarredPage,
 };
 use crate::router::Route;
 use yew::{html, Html};
@@ -9,6 +10,15 @@ pub fn switch(routes: &Route) -> Html {
         Route::Home => html! {
             <HomePage />
         },
+        Route::Feed { id } => html! {
+            <HomePage feed_id={Some(id.clone())} />
+        },
+        Route::Folder { id } => html! {
+            <HomePage folder_id={Some(id.clone())} />
+        },
+        Route::Article { id } => html! {
+            <ArticlePage id={id.clone()} />
+        },
         Route::SignIn => html! {
             <SignInPage />
         },
@@ -18,6 +28,12 @@ pub fn switch(routes: &Route) -> Html {
         Route::Settings => html! {
             <SettingsPage />
         },
+        Route::Search => html! {
+            <SearchPage />
+        },
+        Route::Starred => html! {
+            <StarredPage />
+        },
         Route::NotFound => html! { <h1>{ "404" }</h1> },
     }
 }
@@ -0,0 +1,619 @@
+/// Allow-list HTML sanitizer for feed content.
+///
+/// The input is tokenized and re-serialized: only allowed tags and attributes
+/// are written back, text and attribute values are always escaped and every
+/// tag that is opened is closed, so nothing in a feed can run script in our
+/// origin or break out of the element the html is rendered into.
+pub fn sanitize(html: &str) -> String {
+    Sanitizer::new(html).run()
+}
+
+/// Plain text of feed html, with whitespace collapsed.
+pub fn strip_tags(html: &str) -> String {
+    // Sanitized output has every `>` in text and attributes escaped.
+    let clean = sanitize(html);
+    let mut text = String::with_capacity(clean.len());
+    let mut rest = clean.as_str();
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        text.push(' ');
+        rest = match rest[start..].find('>') {
+            Some(end) => &rest[start + end + 1..],
+            None => "",
+        };
+    }
+    text.push_str(rest);
+    decode_entities(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "article",
+    "b",
+    "blockquote",
+    "br",
+    "caption",
+    "cite",
+    "code",
+    "dd",
+    "del",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "i",
+    "img",
+    "ins",
+    "kbd",
+    "li",
+    "mark",
+    "ol",
+    "p",
+    "pre",
+    "q",
+    "s",
+    "section",
+    "small",
+    "span",
+    "strong",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "tr",
+    "u",
+    "ul",
+];
+
+const VOID_TAGS: &[&str] = &["br", "hr", "img"];
+
+/// Tags that are dropped together with everything up to their closing tag.
+const DROPPED_WITH_CONTENT: &[&str] = &[
+    "applet",
+    "embed",
+    "frame",
+    "frameset",
+    "head",
+    "iframe",
+    "math",
+    "noembed",
+    "noframes",
+    "noscript",
+    "object",
+    "plaintext",
+    "script",
+    "select",
+    "style",
+    "svg",
+    "template",
+    "textarea",
+    "title",
+    "xmp",
+];
+
+/// Only iframes pointing at these players survive sanitizing.
+const ALLOWED_EMBEDS: &[&str] = &[
+    "https://www.youtube.com/embed/",
+    "https://www.youtube-nocookie.com/embed/",
+    "https://player.vimeo.com/video/",
+];
+
+pub(crate) const LINK_SCHEMES: &[&str] = &["http", "https", "mailto"];
+const MEDIA_SCHEMES: &[&str] = &["http", "https"];
+
+enum Attr {
+    Plain,
+    Url(&'static [&'static str]),
+}
+
+fn allowed_attr(tag: &str, attr: &str) -> Option<Attr> {
+    match (tag, attr) {
+        (_, "title") | (_, "lang") | (_, "dir") => Some(Attr::Plain),
+        ("a", "href") => Some(Attr::Url(LINK_SCHEMES)),
+        ("img", "src") => Some(Attr::Url(MEDIA_SCHEMES)),
+        ("img", "alt") | ("img", "width") | ("img", "height") => Some(Attr::Plain),
+        ("td", "colspan") | ("td", "rowspan") | ("th", "colspan") | ("th", "rowspan") => {
+            Some(Attr::Plain)
+        }
+        ("ol", "start") | ("time", "datetime") => Some(Attr::Plain),
+        ("blockquote", "cite") | ("q", "cite") | ("del", "cite") | ("ins", "cite") => {
+            Some(Attr::Url(MEDIA_SCHEMES))
+        }
+        _ => None,
+    }
+}
+
+struct Sanitizer<'a> {
+    input: &'a str,
+    /// ASCII-lowercased copy of `input`, byte offsets match.
+    lower: String,
+    pos: usize,
+    output: String,
+    open: Vec<&'static str>,
+}
+
+impl<'a> Sanitizer<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            lower: input.to_ascii_lowercase(),
+            pos: 0,
+            output: String::with_capacity(input.len()),
+            open: vec![],
+        }
+    }
+
+    fn run(mut self) -> String {
+        while self.pos < self.input.len() {
+            match self.input[self.pos..].find('<') {
+                Some(offset) => {
+                    let end = self.pos + offset;
+                    self.text(end);
+                    self.markup();
+                }
+                None => {
+                    self.text(self.input.len());
+                }
+            }
+        }
+        while let Some(tag) = self.open.pop() {
+            self.close_tag(tag);
+        }
+        self.output
+    }
+
+    fn text(&mut self, end: usize) {
+        let text = &self.input[self.pos..end];
+        escape_text(text, &mut self.output);
+        self.pos = end;
+    }
+
+    fn markup(&mut self) {
+        let rest = &self.input[self.pos..];
+        if rest.starts_with("<!--") {
+            self.skip_past(4, "-->");
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            self.skip_past(2, ">");
+        } else if let Some(closing) = rest.strip_prefix("</") {
+            if starts_with_alpha(closing) {
+                self.pos += 2;
+                let name = self.tag_name();
+                self.skip_past(0, ">");
+                self.end_tag(&name);
+            } else {
+                self.skip_past(2, ">");
+            }
+        } else if starts_with_alpha(&rest[1..]) {
+            self.pos += 1;
+            let name = self.tag_name();
+            if let Some(attrs) = self.attributes() {
+                self.start_tag(&name, attrs);
+            }
+        } else {
+            self.output.push_str("&lt;");
+            self.pos += 1;
+        }
+    }
+
+    fn tag_name(&mut self) -> String {
+        let rest = &self.lower[self.pos..];
+        let len = rest
+            .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+            .unwrap_or(rest.len());
+        self.pos += len;
+        rest[..len].to_string()
+    }
+
+    /// Parses attributes up to the end of the tag, `None` when the tag is never closed.
+    fn attributes(&mut self) -> Option<Vec<(String, String)>> {
+        let mut attrs: Vec<(String, String)> = vec![];
+        loop {
+            self.skip_while(|c| c.is_ascii_whitespace() || c == '/');
+            let rest = &self.input[self.pos..];
+            if rest.is_empty() {
+                return None;
+            }
+            if rest.starts_with('>') {
+                self.pos += 1;
+                return Some(attrs);
+            }
+            let first = rest.chars().next().map(char::len_utf8).unwrap_or(1);
+            let len = rest[first..]
+                .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>' || c == '=')
+                .map(|len| len + first)
+                .unwrap_or(rest.len());
+            let name = self.lower[self.pos..self.pos + len].to_string();
+            self.pos += len;
+            self.skip_while(|c| c.is_ascii_whitespace());
+            let mut value = String::new();
+            if self.input[self.pos..].starts_with('=') {
+                self.pos += 1;
+                self.skip_while(|c| c.is_ascii_whitespace());
+                value = self.attribute_value();
+            }
+            if !attrs.iter().any(|(existing, _)| *existing == name) {
+                attrs.push((name, value));
+            }
+        }
+    }
+
+    fn attribute_value(&mut self) -> String {
+        let rest = &self.input[self.pos..];
+        let quote = match rest.chars().next() {
+            Some(quote) if quote == '"' || quote == '\'' => quote,
+            _ => {
+                let len = rest
+                    .find(|c: char| c.is_ascii_whitespace() || c == '>')
+                    .unwrap_or(rest.len());
+                self.pos += len;
+                return decode_entities(&rest[..len]);
+            }
+        };
+        let len = rest[1..].find(quote).unwrap_or(rest.len() - 1);
+        self.pos += (len + 2).min(rest.len());
+        decode_entities(&rest[1..1 + len])
+    }
+
+    fn start_tag(&mut self, name: &str, attrs: Vec<(String, String)>) {
+        if name == "iframe" {
+            self.embed(&attrs);
+            self.skip_raw(name);
+            return;
+        }
+        if DROPPED_WITH_CONTENT.contains(&name) {
+            self.skip_raw(name);
+            return;
+        }
+        let tag = match ALLOWED_TAGS.iter().find(|tag| **tag == name) {
+            Some(tag) => *tag,
+            None => return,
+        };
+        self.output.push('<');
+        self.output.push_str(tag);
+        for (attr, value) in attrs {
+            let value = match allowed_attr(tag, &attr) {
+                Some(Attr::Plain) => value,
+                Some(Attr::Url(schemes)) => match safe_url(&value, schemes) {
+                    Some(url) => url,
+                    None => continue,
+                },
+                None => continue,
+            };
+            write_attr(&attr, &value, &mut self.output);
+        }
+        match tag {
+            "a" => self
+                .output
+                .push_str(" target=\"_blank\" rel=\"noopener noreferrer nofollow\""),
+            "img" => self.output.push_str(" loading=\"lazy\""),
+            _ => {}
+        }
+        self.output.push('>');
+        if !VOID_TAGS.contains(&tag) {
+            self.open.push(tag);
+        }
+    }
+
+    fn embed(&mut self, attrs: &[(String, String)]) {
+        let src = attrs
+            .iter()
+            .find(|(attr, _)| attr == "src")
+            .and_then(|(_, value)| safe_url(value, MEDIA_SCHEMES))
+            .filter(|src| ALLOWED_EMBEDS.iter().any(|prefix| src.starts_with(prefix)));
+        let src = match src {
+            Some(src) => src,
+            None => return,
+        };
+        self.output.push_str("<iframe");
+        write_attr("src", &src, &mut self.output);
+        for (attr, value) in attrs {
+            if attr == "width" || attr == "height" || attr == "title" {
+                write_attr(attr, value, &mut self.output);
+            }
+        }
+        self.output.push_str(
+            " sandbox=\"allow-scripts allow-same-origin allow-presentation\" allowfullscreen></iframe>",
+        );
+    }
+
+    fn end_tag(&mut self, name: &str) {
+        if let Some(index) = self.open.iter().rposition(|tag| *tag == name) {
+            let closed: Vec<&'static str> = self.open.drain(index..).rev().collect();
+            for tag in closed {
+                self.close_tag(tag);
+            }
+        }
+    }
+
+    fn close_tag(&mut self, tag: &str) {
+        self.output.push_str("</");
+        self.output.push_str(tag);
+        self.output.push('>');
+    }
+
+    /// Skips everything up to and including the closing tag of `name`.
+    fn skip_raw(&mut self, name: &str) {
+        let closing = format!("</{}", name);
+        match self.lower[self.pos..].find(&closing) {
+            Some(offset) => {
+                self.pos += offset + closing.len();
+                self.skip_past(0, ">");
+            }
+            None => self.pos = self.input.len(),
+        }
+    }
+
+    fn skip_past(&mut self, offset: usize, pattern: &str) {
+        let from = (self.pos + offset).min(self.input.len());
+        self.pos = match self.input[from..].find(pattern) {
+            Some(index) => from + index + pattern.len(),
+            None => self.input.len(),
+        };
+    }
+
+    fn skip_while(&mut self, predicate: impl Fn(char) -> bool) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+    }
+}
+
+fn starts_with_alpha(text: &str) -> bool {
+    text.chars()
+        .next()
+        .map(|c| c.is_ascii_alphabetic())
+        .unwrap_or(false)
+}
+
+/// Returns the url when it is relative or uses one of `schemes`.
+pub(crate) fn safe_url(value: &str, schemes: &[&str]) -> Option<String> {
+    let url = value.trim();
+    // Browsers ignore tabs, newlines and control characters inside the scheme.
+    let compact: String = url
+        .chars()
+        .filter(|c| !c.is_control() && !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let scheme_end = compact.find([':', '/', '?', '#']);
+    if let Some(index) = scheme_end {
+        if compact[index..].starts_with(':') && !schemes.contains(&&compact[..index]) {
+            return None;
+        }
+    }
+    Some(url.to_string())
+}
+
+fn write_attr(name: &str, value: &str, output: &mut String) {
+    output.push(' ');
+    output.push_str(name);
+    output.push_str("=\"");
+    for c in value.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '"' => output.push_str("&quot;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            _ => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+/// Escapes markup characters, keeping well formed character references.
+fn escape_text(text: &str, output: &mut String) {
+    for (index, c) in text.char_indices() {
+        match c {
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '&' if entity_len(&text[index..]).is_none() => output.push_str("&amp;"),
+            _ => output.push(c),
+        }
+    }
+}
+
+/// Length of the character reference at the start of `text`, including `&` and `;`.
+fn entity_len(text: &str) -> Option<usize> {
+    let body = text.strip_prefix('&')?;
+    let end = body.find(';')?;
+    let name = &body[..end];
+    let valid = if let Some(hex) = name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+        !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit())
+    } else if let Some(digits) = name.strip_prefix('#') {
+        !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+    } else {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric())
+    };
+    if valid {
+        Some(end + 2)
+    } else {
+        None
+    }
+}
+
+/// Decodes numeric and the common named character references.
+pub fn decode_entities(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find('&') {
+        output.push_str(&rest[..index]);
+        rest = &rest[index..];
+        match decode_entity(rest) {
+            Some((c, len)) => {
+                output.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                output.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+fn decode_entity(text: &str) -> Option<(char, usize)> {
+    let body = &text[1..];
+    if let Some(number) = body.strip_prefix('#') {
+        let (digits, radix, prefix) = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => (hex, 16, 2),
+            None => (number, 10, 1),
+        };
+        let len = digits
+            .find(|c: char| !c.is_digit(radix))
+            .unwrap_or(digits.len());
+        if len == 0 {
+            return None;
+        }
+        let code = u32::from_str_radix(&digits[..len], radix).ok()?;
+        let c = char::from_u32(code).unwrap_or('\u{FFFD}');
+        let semicolon = digits[len..].starts_with(';') as usize;
+        return Some((c, 1 + prefix + len + semicolon));
+    }
+    let end = body.find(';')?;
+    let c = match &body[..end] {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{A0}',
+        "colon" => ':',
+        "Tab" => '\t',
+        "NewLine" => '\n',
+        _ => return None,
+    };
+    Some((c, end + 2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hostile snippets with the exact output expected for them.
+    const CORPUS: &[(&str, &str)] = &[
+        ("<p>hi<script>alert(1)</script></p>", "<p>hi</p>"),
+        ("<SCRIPT SRC=//evil.js></SCRIPT>ok", "ok"),
+        ("<script>document.write('</scr' + 'ipt>')</script>x", "x"),
+        ("<img src=x onerror=alert(1)>", "<img src=\"x\" loading=\"lazy\">"),
+        ("<p onclick=\"steal()\" title=\"t\">x</p>", "<p title=\"t\">x</p>"),
+        ("<a href=\"javascript:alert(1)\">x</a>", "<a target=\"_blank\" rel=\"noopener noreferrer nofollow\">x</a>"),
+        ("<a href=\"data:text/html,<script>alert(1)</script>\">x</a>", "<a target=\"_blank\" rel=\"noopener noreferrer nofollow\">x</a>"),
+        ("<a href=\"jav&#x09;ascript:alert(1)\">x</a>", "<a target=\"_blank\" rel=\"noopener noreferrer nofollow\">x</a>"),
+        ("<a href=\"&#106;avascript:alert(1)\">x</a>", "<a target=\"_blank\" rel=\"noopener noreferrer nofollow\">x</a>"),
+        ("<a href=\" JaVaScRiPt:alert(1)\">x</a>", "<a target=\"_blank\" rel=\"noopener noreferrer nofollow\">x</a>"),
+        ("<a href=\"java\0script:alert(1)\">x</a>", "<a target=\"_blank\" rel=\"noopener noreferrer nofollow\">x</a>"),
+        ("<img src=\"data:image/svg+xml;base64,PHN2Zz4=\">", "<img loading=\"lazy\">"),
+        ("<a href=\"https://example.com/?a=1&amp;b=2\">x</a>", "<a href=\"https://example.com/?a=1&amp;b=2\" target=\"_blank\" rel=\"noopener noreferrer nofollow\">x</a>"),
+        ("<a href=\"/relative\">x</a>", "<a href=\"/relative\" target=\"_blank\" rel=\"noopener noreferrer nofollow\">x</a>"),
+        ("<iframe src=\"https://evil.example/embed\"></iframe>after", "after"),
+        ("<iframe src=\"javascript:alert(1)\"></iframe>", ""),
+        ("<svg><script>alert(1)</script></svg>ok", "ok"),
+        ("<svg onload=alert(1)>", ""),
+        ("<style>body{background:url(javascript:alert(1))}</style>ok", "ok"),
+        ("<math><mi xlink:href=\"javascript:alert(1)\">x</mi></math>", ""),
+        ("<object data=\"evil.swf\"></object><embed src=\"evil.swf\">", ""),
+        ("<p>unclosed <b>bold", "<p>unclosed <b>bold</b></p>"),
+        ("<b><i>mis</b>nested</i>", "<b><i>mis</i></b>nested"),
+        ("</div>stray close", "stray close"),
+        ("<img src=\"x\" onerror=\"alert(1)\"", ""),
+        ("1 < 2 && 3 > 2", "1 &lt; 2 &amp;&amp; 3 &gt; 2"),
+        ("<!-- <script>alert(1)</script> -->ok", "ok"),
+        ("<p style=\"background:url(javascript:alert(1))\">x</p>", "<p>x</p>"),
+        ("<a href=\"x\" target=\"_self\">x</a>", "<a href=\"x\" target=\"_blank\" rel=\"noopener noreferrer nofollow\">x</a>"),
+        ("<img src=\"x\" alt=\"\"><img/src=\"y\">", "<img src=\"x\" alt=\"\" loading=\"lazy\"><img src=\"y\" loading=\"lazy\">"),
+        ("<p title='\"><script>alert(1)</script>'>x</p>", "<p title=\"&quot;&gt;&lt;script&gt;alert(1)&lt;/script&gt;\">x</p>"),
+    ];
+
+    #[test]
+    fn hostile_corpus() {
+        for (input, expected) in CORPUS {
+            assert_eq!(sanitize(input), *expected, "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn output_has_no_script_vectors() {
+        for (input, _) in CORPUS {
+            let output = sanitize(input).to_ascii_lowercase();
+            assert!(!output.contains("<script"), "input: {}", input);
+            assert!(!output.contains("<svg"), "input: {}", input);
+            assert!(!output.contains("<style"), "input: {}", input);
+            assert!(!output.contains(" on"), "input: {}", input);
+            assert!(!output.contains("javascript:"), "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn allowed_embeds_are_kept_and_sandboxed() {
+        let output = sanitize(
+            "<iframe src=\"https://www.youtube.com/embed/abc\" width=\"560\" onload=\"x()\"></iframe>",
+        );
+        assert_eq!(
+            output,
+            "<iframe src=\"https://www.youtube.com/embed/abc\" width=\"560\" \
+             sandbox=\"allow-scripts allow-same-origin allow-presentation\" allowfullscreen></iframe>"
+        );
+        assert_eq!(
+            sanitize("<iframe src=\"https://www.youtube.com.evil.example/embed/abc\"></iframe>"),
+            ""
+        );
+        assert_eq!(
+            sanitize("<iframe src=\"http://player.vimeo.com/video/1\"></iframe>"),
+            ""
+        );
+    }
+
+    #[test]
+    fn safe_url_rejects_obfuscated_schemes() {
+        for url in [
+            "javascript:alert(1)",
+            "JAVASCRIPT:alert(1)",
+            "jav\tascript:alert(1)",
+            "jav\nascript:alert(1)",
+            "\u{1}javascript:alert(1)",
+            "vbscript:msgbox",
+            "data:text/html,x",
+        ] {
+            assert_eq!(safe_url(url, LINK_SCHEMES), None, "url: {:?}", url);
+        }
+        for url in [
+            "https://example.com",
+            "mailto:me@example.com",
+            "/path",
+            "page?x=1:2",
+        ] {
+            assert!(safe_url(url, LINK_SCHEMES).is_some(), "url: {:?}", url);
+        }
+        assert_eq!(safe_url("mailto:me@example.com", MEDIA_SCHEMES), None);
+    }
+
+    #[test]
+    fn strip_tags_keeps_text_only() {
+        assert_eq!(
+            strip_tags("<p>Hello <b>world</b> &amp; more</p><script>x</script>"),
+            "Hello world & more"
+        );
+    }
+}
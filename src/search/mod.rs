@@ -0,0 +1,255 @@
+use std::collections::{HashMap, HashSet};
+
+/// What was typed into the search box.
+///
+/// Words are matched in any order, `"quoted phrases"` only as written.
+/// `feed:name` limits the results to matching feeds, `is:unread` and
+/// `is:starred` to unread or starred items.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchQuery {
+    pub terms: Vec<String>,
+    /// Each phrase as its words.
+    pub phrases: Vec<Vec<String>>,
+    pub feeds: Vec<String>,
+    pub unread: bool,
+    pub starred: bool,
+}
+
+impl SearchQuery {
+    pub fn parse(input: &str) -> Self {
+        let mut query = SearchQuery::default();
+        let mut rest = input.trim_start();
+        while !rest.is_empty() {
+            let (word, quoted, next) = next_word(rest);
+            rest = next.trim_start();
+            if quoted {
+                let phrase = tokenize(&word);
+                match phrase.len() {
+                    0 => {}
+                    1 => query.terms.extend(phrase),
+                    _ => query.phrases.push(phrase),
+                }
+                continue;
+            }
+            let lower = word.to_lowercase();
+            if let Some(feed) = lower.strip_prefix("feed:") {
+                let feed = match feed {
+                    "" if rest.starts_with('"') => {
+                        let (feed, _, next) = next_word(rest);
+                        rest = next.trim_start();
+                        feed.to_lowercase()
+                    }
+                    feed => feed.to_string(),
+                };
+                if !feed.trim().is_empty() {
+                    query.feeds.push(feed.trim().to_string());
+                }
+            } else if lower == "is:unread" {
+                query.unread = true;
+            } else if lower == "is:starred" {
+                query.starred = true;
+            } else {
+                query.terms.extend(tokenize(&word));
+            }
+        }
+        query
+    }
+
+    /// Words and phrases without the operators, for the backend.
+    pub fn text(&self) -> String {
+        let mut parts = self.terms.clone();
+        parts.extend(
+            self.phrases
+                .iter()
+                .map(|phrase| format!("\"{}\"", phrase.join(" "))),
+        );
+        parts.join(" ")
+    }
+
+    pub fn has_text(&self) -> bool {
+        !self.terms.is_empty() || !self.phrases.is_empty()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.has_text() && self.feeds.is_empty() && !self.unread && !self.starred
+    }
+}
+
+/// Reads one word or `"quoted phrase"`; returns it, whether it was quoted and the rest.
+fn next_word(input: &str) -> (String, bool, &str) {
+    if let Some(quoted) = input.strip_prefix('"') {
+        return match quoted.find('"') {
+            Some(end) => (quoted[..end].to_string(), true, &quoted[end + 1..]),
+            None => (quoted.to_string(), true, ""),
+        };
+    }
+    let end = input
+        .find(|c: char| c.is_whitespace() || c == '"')
+        .unwrap_or(input.len());
+    // Stops at a quote, so `feed:"two words"` reads the phrase next.
+    (input[..end].to_string(), false, &input[end..])
+}
+
+/// Lowercased words of `text`.
+pub fn tokenize(text: &str) -> Vec<String> {
+    words(text).map(|(_, word)| word.to_lowercase()).collect()
+}
+
+/// Words of `text` with their byte offsets.
+fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(move |word| (word.as_ptr() as usize - text.as_ptr() as usize, word))
+}
+
+/// Small inverted index over titles and plain text bodies.
+pub struct SearchIndex {
+    documents: Vec<Document>,
+    postings: HashMap<String, Vec<usize>>,
+}
+
+struct Document {
+    title: Vec<String>,
+    body: Vec<String>,
+}
+
+/// Weight of a match in the title against one in the body.
+const TITLE_WEIGHT: usize = 3;
+
+impl SearchIndex {
+    pub fn new<'a>(documents: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let mut postings: HashMap<String, Vec<usize>> = HashMap::new();
+        let documents: Vec<Document> = documents
+            .into_iter()
+            .map(|(title, body)| Document {
+                title: tokenize(title),
+                body: tokenize(body),
+            })
+            .collect();
+        for (index, document) in documents.iter().enumerate() {
+            let unique: HashSet<&String> = document.title.iter().chain(&document.body).collect();
+            for word in unique {
+                postings.entry(word.clone()).or_default().push(index);
+            }
+        }
+        Self {
+            documents,
+            postings,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Indexes of the documents with every word and phrase of the query, best first.
+    /// Operators are left to the caller.
+    pub fn search(&self, query: &SearchQuery) -> Vec<usize> {
+        let words: HashSet<&String> = query
+            .terms
+            .iter()
+            .chain(query.phrases.iter().flatten())
+            .collect();
+        let mut candidates: Option<Vec<usize>> = None;
+        for word in words {
+            let postings = match self.postings.get(word) {
+                Some(postings) => postings,
+                None => return vec![],
+            };
+            candidates = Some(match candidates {
+                Some(candidates) => candidates
+                    .into_iter()
+                    .filter(|index| postings.binary_search(index).is_ok())
+                    .collect(),
+                None => postings.clone(),
+            });
+        }
+        let candidates = candidates.unwrap_or_else(|| (0..self.documents.len()).collect());
+        let mut scored: Vec<(usize, usize)> = candidates
+            .into_iter()
+            .filter_map(|index| {
+                let document = &self.documents[index];
+                let phrases = query.phrases.iter().all(|phrase| {
+                    contains_phrase(&document.title, phrase)
+                        || contains_phrase(&document.body, phrase)
+                });
+                phrases.then(|| (index, score(document, query)))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        scored.into_iter().map(|(index, _)| index).collect()
+    }
+}
+
+fn contains_phrase(words: &[String], phrase: &[String]) -> bool {
+    words.windows(phrase.len()).any(|window| window == phrase)
+}
+
+fn score(document: &Document, query: &SearchQuery) -> usize {
+    let count = |words: &[String]| {
+        words
+            .iter()
+            .filter(|word| {
+                query.terms.contains(word) || query.phrases.iter().flatten().any(|w| &w == word)
+            })
+            .count()
+    };
+    count(&document.title) * TITLE_WEIGHT + count(&document.body)
+}
+
+/// Splits `text` into parts, marking the ones that match the query.
+pub fn highlight(text: &str, query: &SearchQuery) -> Vec<(String, bool)> {
+    let words: Vec<(usize, &str)> = words(text).collect();
+    let lower: Vec<String> = words.iter().map(|(_, word)| word.to_lowercase()).collect();
+    let mut ranges: Vec<(usize, usize)> = vec![];
+    for (index, (start, word)) in words.iter().enumerate() {
+        if query.terms.contains(&lower[index]) {
+            ranges.push((*start, start + word.len()));
+        }
+        for phrase in &query.phrases {
+            if lower[index..].starts_with(phrase) {
+                let (last_start, last) = words[index + phrase.len() - 1];
+                ranges.push((*start, last_start + last.len()));
+            }
+        }
+    }
+    ranges.sort();
+    let mut parts = vec![];
+    let mut pos = 0;
+    for (start, end) in ranges {
+        if end <= pos {
+            continue;
+        }
+        let start = start.max(pos);
+        if start > pos {
+            parts.push((text[pos..start].to_string(), false));
+        }
+        parts.push((text[start..end].to_string(), true));
+        pos = end;
+    }
+    if pos < text.len() {
+        parts.push((text[pos..].to_string(), false));
+    }
+    parts
+}
+
+/// About `length` characters of `text` around the first match.
+pub fn snippet(text: &str, query: &SearchQuery, length: usize) -> String {
+    let first = highlight(text, query)
+        .iter()
+        .take_while(|(_, matched)| !matched)
+        .map(|(part, _)| part.chars().count())
+        .sum::<usize>();
+    let total = text.chars().count();
+    let start = first
+        .saturating_sub(length / 4)
+        .min(total.saturating_sub(length));
+    let mut snippet: String = text.chars().skip(start).take(length).collect();
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if start + length < total {
+        snippet.push('…');
+    }
+    snippet
+}
@@ -0,0 +1,52 @@
+use super::user_data;
+use crate::dto::ContentDto;
+use crate::sanitize::strip_tags;
+use serde::{Deserialize, Serialize};
+
+const CACHE_KEY: &str = "rss-reader.content";
+/// Items kept at most, the ones seen longest ago are dropped first.
+const MAX_ITEMS: usize = 1000;
+/// Characters of the description kept per item.
+const MAX_TEXT: usize = 1000;
+
+/// Items seen in the news stream, so search works without the backend.
+///
+/// Descriptions are kept as shortened plain text to stay within the
+/// storage quota.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ContentCache {
+    pub items: Vec<ContentDto>,
+}
+
+impl Default for ContentCache {
+    fn default() -> Self {
+        user_data::load(CACHE_KEY).unwrap_or(Self { items: vec![] })
+    }
+}
+
+impl ContentCache {
+    pub fn add(&mut self, items: &[ContentDto]) {
+        let mut added: Vec<ContentDto> = items
+            .iter()
+            .map(|item| ContentDto {
+                description: item
+                    .description
+                    .as_deref()
+                    .map(|html| strip_tags(html).chars().take(MAX_TEXT).collect()),
+                content: None,
+                ..item.clone()
+            })
+            .collect();
+        self.items.retain(|cached| {
+            !added
+                .iter()
+                .any(|item| item.content_id == cached.content_id)
+        });
+        added.append(&mut self.items);
+        added.truncate(MAX_ITEMS);
+        self.items = added;
+        if let Err(error) = user_data::save(CACHE_KEY, &*self) {
+            log::error!("failed to cache the content: {}", error);
+        }
+    }
+}
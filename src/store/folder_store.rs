@@ -0,0 +1,127 @@
+use super::user_data;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+const FOLDERS_KEY: &str = "rss-reader.folders";
+
+/// User-defined folders grouping the subscriptions, kept in local storage.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct FolderStore {
+    pub folders: Vec<Folder>,
+    /// Folder of each `link_id`; links without an entry are unfiled.
+    #[serde(default)]
+    pub assignments: BTreeMap<String, String>,
+    /// Folders collapsed in the sidebar.
+    #[serde(default)]
+    pub collapsed: BTreeSet<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Folder {
+    pub folder_id: String,
+    pub name: String,
+}
+
+impl Default for FolderStore {
+    fn default() -> Self {
+        user_data::load(FOLDERS_KEY).unwrap_or(Self {
+            folders: vec![],
+            assignments: BTreeMap::new(),
+            collapsed: BTreeSet::new(),
+        })
+    }
+}
+
+impl FolderStore {
+    pub fn folder(&self, folder_id: &str) -> Option<&Folder> {
+        self.folders
+            .iter()
+            .find(|folder| folder.folder_id == folder_id)
+    }
+
+    pub fn folder_of(&self, link_id: &str) -> Option<&str> {
+        self.assignments.get(link_id).map(String::as_str)
+    }
+
+    /// Links filed in the folder.
+    pub fn link_ids(&self, folder_id: &str) -> Vec<String> {
+        self.assignments
+            .iter()
+            .filter(|(_, folder)| folder.as_str() == folder_id)
+            .map(|(link_id, _)| link_id.clone())
+            .collect()
+    }
+
+    pub fn is_collapsed(&self, folder_id: &str) -> bool {
+        self.collapsed.contains(folder_id)
+    }
+
+    /// Creates a folder, or returns the existing one with the same name.
+    pub fn create(&mut self, name: &str) -> String {
+        let name = name.trim();
+        if let Some(folder) = self
+            .folders
+            .iter()
+            .find(|folder| folder.name.eq_ignore_ascii_case(name))
+        {
+            return folder.folder_id.clone();
+        }
+        let folder_id = format!(
+            "{:x}{:x}",
+            js_sys::Date::now() as u64,
+            (js_sys::Math::random() * 65536.0) as u32
+        );
+        self.folders.push(Folder {
+            folder_id: folder_id.clone(),
+            name: name.to_string(),
+        });
+        self.save();
+        folder_id
+    }
+
+    pub fn rename(&mut self, folder_id: &str, name: &str) {
+        if let Some(folder) = self
+            .folders
+            .iter_mut()
+            .find(|folder| folder.folder_id == folder_id)
+        {
+            folder.name = name.trim().to_string();
+        }
+        self.save();
+    }
+
+    /// Deletes the folder; its links become unfiled.
+    pub fn delete(&mut self, folder_id: &str) {
+        self.folders.retain(|folder| folder.folder_id != folder_id);
+        self.assignments.retain(|_, folder| folder != folder_id);
+        self.collapsed.remove(folder_id);
+        self.save();
+    }
+
+    /// Files the link in the folder, or unfiles it with `None`.
+    pub fn move_link(&mut self, link_id: &str, folder_id: Option<&str>) {
+        match folder_id {
+            Some(folder_id) if self.folder(folder_id).is_some() => {
+                self.assignments
+                    .insert(link_id.to_string(), folder_id.to_string());
+            }
+            _ => {
+                self.assignments.remove(link_id);
+            }
+        }
+        self.save();
+    }
+
+    pub fn toggle_collapsed(&mut self, folder_id: &str) {
+        if !self.collapsed.remove(folder_id) {
+            self.collapsed.insert(folder_id.to_string());
+        }
+        self.save();
+    }
+
+    fn save(&self) {
+        if let Err(error) = user_data::save(FOLDERS_KEY, self) {
+            log::error!("failed to persist folders: {}", error);
+        }
+    }
+}
@@ -1,4 +1,20 @@
+use crate::dto::LinkDto;
+
+/// Subscriptions of the current user, shared by the settings components.
 #[derive(Clone, Default)]
 pub struct LinkStore {
-    pub link_ids: Vec<String>,
+    pub links: Vec<LinkDto>,
+}
+
+impl LinkStore {
+    /// Replaces the link with the same `link_id`.
+    pub fn replace(&mut self, link: LinkDto) {
+        if let Some(current) = self
+            .links
+            .iter_mut()
+            .find(|current| current.link_id == link.link_id)
+        {
+            *current = link;
+        }
+    }
 }
@@ -1,6 +1,24 @@
+mod content_cache;
+mod folder_store;
+mod jwt;
 mod link_store;
+mod player_store;
+mod read_store;
+mod session;
+mod settings_store;
+mod star_store;
+mod stream_store;
+mod user_data;
 mod user_store;
 
+pub use content_cache::ContentCache;
+pub use folder_store::{Folder, FolderStore};
 pub use link_store::LinkStore;
+pub use player_store::PlayerStore;
+pub use read_store::ReadStore;
+pub use session::listen as listen_session;
+pub use settings_store::{KeyAction, KeyMatch, SettingsStore, PAGE_SIZES};
+pub use star_store::StarStore;
+pub use stream_store::StreamStore;
 pub use user_store::AuthState;
 pub use user_store::UserStore;
@@ -0,0 +1,136 @@
+use super::user_data;
+use crate::dto::ContentDto;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const PLAYER_KEY: &str = "rss-reader.player";
+/// Resume positions kept at most, the oldest are dropped first.
+const MAX_POSITIONS: usize = 200;
+
+/// Podcast player shared by the news items and the mini-player.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct PlayerStore {
+    pub episode: Option<Episode>,
+    /// Not persisted, a reload never starts playing on its own.
+    #[serde(skip)]
+    pub playing: bool,
+    pub rate: f64,
+    /// Resume position in seconds per `content_id`.
+    #[serde(default)]
+    pub positions: BTreeMap<String, Position>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Episode {
+    pub content_id: String,
+    pub title: String,
+    pub url: String,
+    pub image_url: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Position {
+    pub seconds: f64,
+    /// When the position was saved, in ms since the unix epoch.
+    pub saved_at: f64,
+}
+
+impl Default for PlayerStore {
+    fn default() -> Self {
+        user_data::load(PLAYER_KEY).unwrap_or(Self {
+            episode: None,
+            playing: false,
+            rate: 1.0,
+            positions: BTreeMap::new(),
+        })
+    }
+}
+
+impl PlayerStore {
+    pub fn is_playing(&self, content_id: &str) -> bool {
+        self.playing && self.is_current(content_id)
+    }
+
+    pub fn is_current(&self, content_id: &str) -> bool {
+        self.episode
+            .as_ref()
+            .is_some_and(|episode| episode.content_id == content_id)
+    }
+
+    pub fn position(&self, content_id: &str) -> Option<f64> {
+        self.positions
+            .get(content_id)
+            .map(|position| position.seconds)
+    }
+
+    /// Plays the audio enclosure of the item, or pauses it when it is playing.
+    pub fn toggle(&mut self, content: &ContentDto) {
+        if self.is_current(&content.content_id) {
+            self.playing = !self.playing;
+            self.save();
+            return;
+        }
+        let audio = match content.audio() {
+            Some(audio) => audio,
+            None => return,
+        };
+        self.episode = Some(Episode {
+            content_id: content.content_id.clone(),
+            title: content.title.clone(),
+            url: audio.url.clone(),
+            image_url: content.image_url.clone(),
+        });
+        self.playing = true;
+        self.save();
+    }
+
+    pub fn set_playing(&mut self, playing: bool) {
+        self.playing = playing;
+    }
+
+    pub fn set_rate(&mut self, rate: f64) {
+        self.rate = rate;
+        self.save();
+    }
+
+    pub fn save_position(&mut self, content_id: &str, seconds: f64) {
+        self.positions.insert(
+            content_id.to_string(),
+            Position {
+                seconds,
+                saved_at: js_sys::Date::now(),
+            },
+        );
+        while self.positions.len() > MAX_POSITIONS {
+            let oldest = self
+                .positions
+                .iter()
+                .min_by(|a, b| a.1.saved_at.total_cmp(&b.1.saved_at))
+                .map(|(content_id, _)| content_id.clone());
+            match oldest {
+                Some(content_id) => self.positions.remove(&content_id),
+                None => break,
+            };
+        }
+        self.save();
+    }
+
+    /// Forgets the position of an episode that was played to the end.
+    pub fn finish(&mut self, content_id: &str) {
+        self.positions.remove(content_id);
+        self.playing = false;
+        self.save();
+    }
+
+    pub fn close(&mut self) {
+        self.episode = None;
+        self.playing = false;
+        self.save();
+    }
+
+    fn save(&self) {
+        if let Err(error) = user_data::save(PLAYER_KEY, self) {
+            log::error!("failed to persist the player: {}", error);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use super::user_data;
+use crate::date::parse_date;
+use crate::dto::ContentDto;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+const READ_KEY: &str = "rss-reader.read";
+/// Upper bound of ids kept locally, the oldest marks are dropped first.
+const MAX_IDS: usize = 5000;
+
+/// Read state kept in `localStorage`, used alongside the backend and instead
+/// of it when the backend does not track read state.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ReadStore {
+    /// Ids marked as read, oldest first.
+    read: VecDeque<String>,
+    /// Same ids as `read`, for lookups.
+    #[serde(skip)]
+    read_ids: HashSet<String>,
+    unread: HashSet<String>,
+    /// Items published before this moment (ms since the unix epoch) are read.
+    read_before: Option<f64>,
+    /// Same as `read_before`, for the items of single feeds.
+    #[serde(default)]
+    feed_read_before: HashMap<String, f64>,
+}
+
+impl Default for ReadStore {
+    fn default() -> Self {
+        let mut store: Self = user_data::load(READ_KEY).unwrap_or(Self {
+            read: VecDeque::new(),
+            read_ids: HashSet::new(),
+            unread: HashSet::new(),
+            read_before: None,
+            feed_read_before: HashMap::new(),
+        });
+        store.read_ids = store.read.iter().cloned().collect();
+        store
+    }
+}
+
+impl ReadStore {
+    pub fn is_read(&self, content: &ContentDto) -> bool {
+        if self.unread.contains(&content.content_id) {
+            return false;
+        }
+        content.read
+            || self.read_ids.contains(&content.content_id)
+            || match (self.read_before(&content.link_id), published_at(content)) {
+                (Some(read_before), Some(date)) => date <= read_before,
+                _ => false,
+            }
+    }
+
+    pub fn mark_read(&mut self, ids: &[String]) {
+        for id in ids {
+            self.unread.remove(id);
+            if self.read_ids.insert(id.clone()) {
+                self.read.push_back(id.clone());
+            }
+        }
+        while self.read.len() > MAX_IDS {
+            if let Some(id) = self.read.pop_front() {
+                self.read_ids.remove(&id);
+            }
+        }
+        self.save();
+    }
+
+    pub fn mark_unread(&mut self, ids: &[String]) {
+        for id in ids {
+            if self.read_ids.remove(id) {
+                self.read.retain(|read| read != id);
+            }
+            self.unread.insert(id.clone());
+        }
+        self.save();
+    }
+
+    /// Marks everything published before `date` (ms since the unix epoch) as
+    /// read, only in the feeds `link_ids` unless that is empty.
+    pub fn mark_read_before(&mut self, date: f64, link_ids: &[String]) {
+        if link_ids.is_empty() {
+            self.read_before = Some(self.read_before.map_or(date, |before| before.max(date)));
+            self.feed_read_before.retain(|_, before| *before > date);
+            self.unread.clear();
+        }
+        for link_id in link_ids {
+            let before = self.feed_read_before.entry(link_id.clone()).or_insert(date);
+            *before = before.max(date);
+        }
+        self.save();
+    }
+
+    fn read_before(&self, link_id: &str) -> Option<f64> {
+        match (self.read_before, self.feed_read_before.get(link_id)) {
+            (Some(all), Some(feed)) => Some(all.max(*feed)),
+            (all, feed) => all.or(feed.copied()),
+        }
+    }
+
+    fn save(&self) {
+        if let Err(error) = user_data::save(READ_KEY, self) {
+            log::error!("failed to persist read state: {}", error);
+        }
+    }
+}
+
+fn published_at(content: &ContentDto) -> Option<f64> {
+    parse_date(&content.date)
+}
@@ -0,0 +1,96 @@
+use super::user_data;
+use super::user_store::{AuthState, UserStore};
+use gloo_events::EventListener;
+use gloo_storage::{LocalStorage, SessionStorage, Storage};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsCast;
+use web_sys::StorageEvent;
+use yewdux::dispatch::{Dispatch, Dispatcher};
+use yewdux::prelude::BasicStore;
+
+const SESSION_KEY: &str = "rss-reader.session";
+const SIGN_OUT_KEY: &str = "rss-reader.sign-out";
+
+/// Part of the `UserStore` that survives a page reload.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Session {
+    pub token: String,
+    pub user_id: String,
+    pub auth_state: AuthState,
+    #[serde(default = "remember_default")]
+    pub remember: bool,
+}
+
+fn remember_default() -> bool {
+    true
+}
+
+impl From<&UserStore> for Session {
+    fn from(store: &UserStore) -> Self {
+        Self {
+            token: store.token.clone(),
+            user_id: store.user_id.clone(),
+            auth_state: store.auth_state.clone(),
+            remember: store.remember,
+        }
+    }
+}
+
+/// Reads the session of the current tab, falling back to the remembered one.
+pub fn load() -> Option<Session> {
+    SessionStorage::get(SESSION_KEY)
+        .or_else(|_| LocalStorage::get(SESSION_KEY))
+        .ok()
+}
+
+/// Saves the session in `localStorage` when `remember` is set, otherwise
+/// only for the lifetime of the tab.
+pub fn save(store: &UserStore, remember: bool) {
+    let session = Session::from(store);
+    let result = if remember {
+        SessionStorage::delete(SESSION_KEY);
+        LocalStorage::set(SESSION_KEY, session)
+    } else {
+        LocalStorage::delete(SESSION_KEY);
+        SessionStorage::set(SESSION_KEY, session)
+    };
+    if let Err(error) = result {
+        log::error!("failed to persist session: {}", error);
+    }
+}
+
+pub fn clear() {
+    SessionStorage::delete(SESSION_KEY);
+    LocalStorage::delete(SESSION_KEY);
+}
+
+/// Tells the other tabs that the user signed out.
+pub fn broadcast_sign_out() {
+    let _ = LocalStorage::set(SIGN_OUT_KEY, js_sys::Date::now());
+}
+
+/// Keeps the store in sync with sign-ins and sign-outs made in other tabs.
+pub fn listen(dispatch: Dispatch<BasicStore<UserStore>>) -> EventListener {
+    EventListener::new(&gloo_utils::window(), "storage", move |event| {
+        let key = match event.dyn_ref::<StorageEvent>().and_then(|e| e.key()) {
+            Some(key) => key,
+            None => return,
+        };
+        if key == SIGN_OUT_KEY {
+            SessionStorage::delete(SESSION_KEY);
+            user_data::reload();
+            dispatch.reduce(|s| s.reset());
+        } else if key == SESSION_KEY {
+            dispatch.reduce(|s| {
+                let owner = s.data_owner();
+                match load() {
+                    Some(session) => s.restore(session),
+                    None => s.reset(),
+                }
+                if s.data_owner() != owner {
+                    user_data::reload();
+                }
+            });
+        }
+    })
+}
@@ -0,0 +1,171 @@
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const SETTINGS_KEY: &str = "rss-reader.settings";
+/// Page sizes offered in the news toolbar.
+pub const PAGE_SIZES: [u32; 4] = [10, 15, 30, 50];
+
+/// Reading preferences of this browser.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct SettingsStore {
+    /// Load the next page when scrolling to the end instead of paging.
+    #[serde(default)]
+    pub infinite_scroll: bool,
+    /// Items per page when the url does not say otherwise.
+    #[serde(default = "default_page_size")]
+    pub page_size: u32,
+    /// Shortcuts changed from their defaults. A binding is a sequence of
+    /// `KeyboardEvent.key` values separated by spaces, e.g. `g s`.
+    #[serde(default)]
+    pub key_bindings: BTreeMap<KeyAction, Vec<String>>,
+}
+
+/// Something the reader can do from the keyboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum KeyAction {
+    NextItem,
+    PreviousItem,
+    Open,
+    Star,
+    ToggleRead,
+    NextPage,
+    PreviousPage,
+    GoToSettings,
+    Help,
+}
+
+impl KeyAction {
+    pub const ALL: [KeyAction; 9] = [
+        KeyAction::NextItem,
+        KeyAction::PreviousItem,
+        KeyAction::Open,
+        KeyAction::Star,
+        KeyAction::ToggleRead,
+        KeyAction::NextPage,
+        KeyAction::PreviousPage,
+        KeyAction::GoToSettings,
+        KeyAction::Help,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            KeyAction::NextItem => "next item",
+            KeyAction::PreviousItem => "previous item",
+            KeyAction::Open => "open the item",
+            KeyAction::Star => "star or unstar",
+            KeyAction::ToggleRead => "mark as read or unread",
+            KeyAction::NextPage => "next page",
+            KeyAction::PreviousPage => "previous page",
+            KeyAction::GoToSettings => "go to settings",
+            KeyAction::Help => "show the shortcuts",
+        }
+    }
+
+    fn default_bindings(&self) -> &'static [&'static str] {
+        match self {
+            KeyAction::NextItem => &["j"],
+            KeyAction::PreviousItem => &["k"],
+            KeyAction::Open => &["o", "Enter"],
+            KeyAction::Star => &["s"],
+            KeyAction::ToggleRead => &["m"],
+            KeyAction::NextPage => &["n"],
+            KeyAction::PreviousPage => &["p"],
+            KeyAction::GoToSettings => &["g s"],
+            KeyAction::Help => &["?"],
+        }
+    }
+}
+
+/// What the keys typed so far stand for.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyMatch {
+    Action(KeyAction),
+    /// Start of a longer binding, wait for the next key.
+    Prefix,
+    None,
+}
+
+fn default_page_size() -> u32 {
+    15
+}
+
+impl Default for SettingsStore {
+    fn default() -> Self {
+        LocalStorage::get(SETTINGS_KEY).unwrap_or(Self {
+            infinite_scroll: false,
+            page_size: default_page_size(),
+            key_bindings: BTreeMap::new(),
+        })
+    }
+}
+
+impl SettingsStore {
+    pub fn toggle_infinite_scroll(&mut self) {
+        self.infinite_scroll = !self.infinite_scroll;
+        self.save();
+    }
+
+    pub fn set_page_size(&mut self, page_size: u32) {
+        self.page_size = page_size;
+        self.save();
+    }
+
+    pub fn bindings(&self, action: KeyAction) -> Vec<String> {
+        match self.key_bindings.get(&action) {
+            Some(bindings) => bindings.clone(),
+            None => action
+                .default_bindings()
+                .iter()
+                .map(|binding| binding.to_string())
+                .collect(),
+        }
+    }
+
+    /// Looks up the sequence of keys typed since the last shortcut.
+    pub fn match_keys(&self, keys: &[String]) -> KeyMatch {
+        let mut prefix = false;
+        for action in KeyAction::ALL {
+            for binding in self.bindings(action) {
+                let binding: Vec<&str> = binding.split_whitespace().collect();
+                if binding.len() < keys.len() || !binding.iter().zip(keys).all(|(a, b)| a == b) {
+                    continue;
+                }
+                if binding.len() == keys.len() {
+                    return KeyMatch::Action(action);
+                }
+                prefix = true;
+            }
+        }
+        if prefix {
+            KeyMatch::Prefix
+        } else {
+            KeyMatch::None
+        }
+    }
+
+    pub fn set_bindings(&mut self, action: KeyAction, bindings: Vec<String>) {
+        let defaults: Vec<String> = action
+            .default_bindings()
+            .iter()
+            .map(|binding| binding.to_string())
+            .collect();
+        if bindings == defaults {
+            self.key_bindings.remove(&action);
+        } else {
+            self.key_bindings.insert(action, bindings);
+        }
+        self.save();
+    }
+
+    pub fn reset_bindings(&mut self) {
+        self.key_bindings.clear();
+        self.save();
+    }
+
+    fn save(&self) {
+        if let Err(error) = LocalStorage::set(SETTINGS_KEY, self) {
+            log::error!("failed to persist the settings: {}", error);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use super::user_data;
+use crate::dto::ContentDto;
+use serde::{Deserialize, Serialize};
+
+const STARRED_KEY: &str = "rss-reader.starred";
+
+/// Starred articles, kept as full snapshots so they outlive the feed window.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct StarStore {
+    pub items: Vec<StarredItem>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct StarredItem {
+    pub content: ContentDto,
+    /// When the item was starred, in ms since the unix epoch.
+    pub starred_at: f64,
+}
+
+impl Default for StarStore {
+    fn default() -> Self {
+        user_data::load(STARRED_KEY).unwrap_or(Self { items: vec![] })
+    }
+}
+
+impl StarStore {
+    pub fn is_starred(&self, content_id: &str) -> bool {
+        self.items
+            .iter()
+            .any(|item| item.content.content_id == content_id)
+    }
+
+    pub fn toggle(&mut self, content: &ContentDto) {
+        if self.is_starred(&content.content_id) {
+            self.remove(&content.content_id);
+            return;
+        }
+        self.items.insert(
+            0,
+            StarredItem {
+                content: content.clone(),
+                starred_at: js_sys::Date::now(),
+            },
+        );
+        self.save();
+    }
+
+    pub fn remove(&mut self, content_id: &str) {
+        self.items
+            .retain(|item| item.content.content_id != content_id);
+        self.save();
+    }
+
+    fn save(&self) {
+        if let Err(error) = user_data::save(STARRED_KEY, self) {
+            log::error!("failed to persist starred items: {}", error);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::dto::ContentDto;
+use crate::router::PageQuery;
+use gloo_storage::{SessionStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+pub(super) const STREAM_KEY: &str = "rss-reader.stream";
+
+/// Items last shown in the news stream, so the reader view can step through
+/// them and lead back. Kept for the tab only.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct StreamStore {
+    pub items: Vec<ContentDto>,
+    /// Path of the stream, e.g. `/feed/42`.
+    pub path: String,
+    pub page: PageQuery,
+}
+
+impl Default for StreamStore {
+    fn default() -> Self {
+        SessionStorage::get(STREAM_KEY).unwrap_or(Self {
+            items: vec![],
+            path: "/".to_string(),
+            page: PageQuery::default(),
+        })
+    }
+}
+
+impl StreamStore {
+    pub fn set(&mut self, items: Vec<ContentDto>, path: String, page: PageQuery) {
+        self.items = items;
+        self.path = path;
+        self.page = page;
+        if let Err(error) = SessionStorage::set(STREAM_KEY, &*self) {
+            log::error!("failed to keep the stream: {}", error);
+        }
+    }
+
+    pub fn find(&self, content_id: &str) -> Option<&ContentDto> {
+        self.items.iter().find(|el| el.content_id == content_id)
+    }
+
+    /// Items before and after `content_id` in the stream.
+    pub fn neighbours(&self, content_id: &str) -> (Option<&ContentDto>, Option<&ContentDto>) {
+        match self.items.iter().position(|el| el.content_id == content_id) {
+            Some(index) => (
+                index.checked_sub(1).and_then(|index| self.items.get(index)),
+                self.items.get(index + 1),
+            ),
+            None => (None, None),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use super::content_cache::ContentCache;
+use super::folder_store::FolderStore;
+use super::player_store::PlayerStore;
+use super::read_store::ReadStore;
+use super::session;
+use super::star_store::StarStore;
+use super::stream_store::{StreamStore, STREAM_KEY};
+use super::user_store::AuthState;
+use gloo_storage::errors::StorageError;
+use gloo_storage::{LocalStorage, SessionStorage, Storage};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use yewdux::dispatch::{Dispatch, Dispatcher};
+use yewdux::prelude::BasicStore;
+
+/// Key of the data `base` of the signed-in user, `None` when signed out.
+///
+/// Tokens without a subject share the plain key.
+fn user_key(base: &str) -> Option<String> {
+    let session = session::load()
+        .filter(|session| session.auth_state == AuthState::Auth && !session.token.is_empty())?;
+    if session.user_id.is_empty() {
+        return Some(base.to_string());
+    }
+    Some(format!("{}.{}", base, session.user_id))
+}
+
+/// Reads the data `base` of the signed-in user from `localStorage`.
+///
+/// Data saved before it was kept per user moves to the first user to sign in.
+pub(super) fn load<T: DeserializeOwned + Serialize>(base: &str) -> Option<T> {
+    let key = user_key(base)?;
+    if let Ok(value) = LocalStorage::get(&key) {
+        return Some(value);
+    }
+    if key == base {
+        return None;
+    }
+    let value: T = LocalStorage::get(base).ok()?;
+    if LocalStorage::set(&key, &value).is_ok() {
+        LocalStorage::delete(base);
+    }
+    Some(value)
+}
+
+/// Writes the data `base` of the signed-in user; nothing is kept signed out.
+pub(super) fn save<T: Serialize>(base: &str, value: &T) -> Result<(), StorageError> {
+    match user_key(base) {
+        Some(key) => LocalStorage::set(key, value),
+        None => Ok(()),
+    }
+}
+
+/// Loads the stores again after the user signed in, out or changed.
+///
+/// Nothing is deleted: starred items and folders only exist in the browser.
+pub fn reload() {
+    SessionStorage::delete(STREAM_KEY);
+    reload_store::<ReadStore>();
+    reload_store::<StarStore>();
+    reload_store::<FolderStore>();
+    reload_store::<PlayerStore>();
+    reload_store::<ContentCache>();
+    reload_store::<StreamStore>();
+}
+
+fn reload_store<T: Clone + Default + 'static>() {
+    Dispatch::<BasicStore<T>>::new().reduce(|s| *s = T::default());
+}
@@ -1,4 +1,9 @@
-#[derive(Clone)]
+use super::jwt::decode_claims;
+use super::session::{self, Session};
+use super::user_data;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub enum AuthState {
     Auth,
     UnAuth,
@@ -10,9 +15,89 @@ impl Default for AuthState {
     }
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct UserStore {
     pub auth_state: AuthState,
     pub token: String,
     pub user_id: String,
+    pub notice: Option<String>,
+    /// Token expiry in milliseconds since the unix epoch, when the token carries one.
+    pub expires_at: Option<f64>,
+    pub remember: bool,
+}
+
+impl Default for UserStore {
+    fn default() -> Self {
+        let mut store = Self {
+            auth_state: Default::default(),
+            token: "".to_string(),
+            user_id: "".to_string(),
+            notice: None,
+            expires_at: None,
+            remember: true,
+        };
+        if let Some(session) = session::load() {
+            store.restore(session);
+        }
+        store
+    }
+}
+
+impl UserStore {
+    pub fn sign_in(&mut self, token: String, remember: bool) {
+        let owner = self.data_owner();
+        self.token = token;
+        self.auth_state = AuthState::Auth;
+        self.notice = None;
+        self.remember = remember;
+        self.read_claims();
+        session::save(self, remember);
+        // A token refresh keeps the user, and the data loaded for them.
+        if self.data_owner() != owner {
+            user_data::reload();
+        }
+    }
+
+    pub fn sign_out(&mut self) {
+        self.reset();
+        session::clear();
+        user_data::reload();
+        session::broadcast_sign_out();
+    }
+
+    /// True when there is a token to call the backend with.
+    pub fn has_token(&self) -> bool {
+        !self.token.is_empty() && self.token != "error"
+    }
+
+    /// User whose local data is loaded, `None` when signed out.
+    pub(super) fn data_owner(&self) -> Option<String> {
+        match self.auth_state {
+            AuthState::Auth => Some(self.user_id.clone()),
+            AuthState::UnAuth => None,
+        }
+    }
+
+    pub(super) fn restore(&mut self, session: Session) {
+        self.token = session.token;
+        self.user_id = session.user_id;
+        self.auth_state = session.auth_state;
+        self.remember = session.remember;
+        self.read_claims();
+    }
+
+    pub(super) fn reset(&mut self) {
+        self.auth_state = AuthState::UnAuth;
+        self.token = "".to_string();
+        self.user_id = "".to_string();
+        self.expires_at = None;
+    }
+
+    fn read_claims(&mut self) {
+        let claims = decode_claims(&self.token).unwrap_or_default();
+        if let Some(sub) = claims.sub {
+            self.user_id = sub;
+        }
+        self.expires_at = claims.exp.map(|exp| exp * 1000.0);
+    }
 }
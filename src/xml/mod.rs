@@ -0,0 +1,314 @@
+use crate::sanitize::decode_entities;
+use std::fmt;
+
+/// Deepest element nesting accepted by `parse`; walking the tree recurses.
+const MAX_DEPTH: usize = 256;
+
+/// Minimal, lenient XML reader for feeds and OPML files.
+///
+/// Builds a tree of elements and text. Namespaces are not resolved, element
+/// names are kept as written (`dc:creator`); `local_name` strips the prefix.
+/// Mismatched closing tags are tolerated, as real-world feeds often have them.
+pub fn parse(input: &str) -> Result<Element, XmlError> {
+    let mut reader = Reader { input, pos: 0 };
+    let mut stack: Vec<Element> = vec![];
+    let mut root: Option<Element> = None;
+    while reader.pos < input.len() {
+        let rest = &input[reader.pos..];
+        if !rest.starts_with('<') {
+            let len = rest.find('<').unwrap_or(rest.len());
+            if let Some(parent) = stack.last_mut() {
+                parent
+                    .children
+                    .push(Node::Text(decode_entities(&rest[..len])));
+            }
+            reader.pos += len;
+        } else if let Some(body) = rest.strip_prefix("<![CDATA[") {
+            let len = body.find("]]>").unwrap_or(body.len());
+            if let Some(parent) = stack.last_mut() {
+                parent.children.push(Node::Text(body[..len].to_string()));
+            }
+            reader.pos += (9 + len + 3).min(rest.len());
+        } else if rest.starts_with("<!--") {
+            reader.skip_past("-->");
+        } else if rest.starts_with("<!") {
+            reader.skip_doctype();
+        } else if rest.starts_with("<?") {
+            reader.skip_past("?>");
+        } else if rest.starts_with("</") {
+            reader.pos += 2;
+            let name = reader.name();
+            reader.skip_past(">");
+            if let Some(index) = stack.iter().rposition(|el| el.name == name) {
+                while stack.len() > index {
+                    let element = stack.pop().unwrap();
+                    close(&mut stack, &mut root, element);
+                }
+            }
+        } else {
+            reader.pos += 1;
+            let name = reader.name();
+            if name.is_empty() {
+                return Err(XmlError(format!("invalid tag at {}", reader.pos)));
+            }
+            let (attributes, self_closing) = reader.attributes()?;
+            let element = Element {
+                name,
+                attributes,
+                children: vec![],
+            };
+            if self_closing {
+                close(&mut stack, &mut root, element);
+            } else if stack.len() >= MAX_DEPTH {
+                return Err(XmlError("document is nested too deeply".to_string()));
+            } else {
+                stack.push(element);
+            }
+        }
+        if root.is_some() && stack.is_empty() {
+            break;
+        }
+    }
+    while let Some(element) = stack.pop() {
+        close(&mut stack, &mut root, element);
+    }
+    root.ok_or_else(|| XmlError("document has no root element".to_string()))
+}
+
+fn close(stack: &mut [Element], root: &mut Option<Element>, element: Element) {
+    match stack.last_mut() {
+        Some(parent) => parent.children.push(Node::Element(element)),
+        None => {
+            if root.is_none() {
+                *root = Some(element);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct XmlError(pub String);
+
+impl fmt::Display for XmlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid xml: {}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Node {
+    Element(Element),
+    Text(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+impl Element {
+    /// Name without the namespace prefix.
+    pub fn local_name(&self) -> &str {
+        local_name(&self.name)
+    }
+
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attr, _)| attr == name || local_name(attr) == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    /// Child elements called `name`, compared with the prefix when `name` has one.
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.elements()
+            .filter(move |element| element.is_named(name))
+    }
+
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.elements().find(|element| element.is_named(name))
+    }
+
+    /// Text of the element and its descendants.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for node in &self.children {
+            match node {
+                Node::Text(value) => text.push_str(value),
+                Node::Element(element) => text.push_str(&element.text()),
+            }
+        }
+        text
+    }
+
+    /// Trimmed text of the first child called `name`, if it has any.
+    pub fn child_text(&self, name: &str) -> Option<String> {
+        self.child(name)
+            .map(|element| element.text().trim().to_string())
+            .filter(|text| !text.is_empty())
+    }
+
+    /// Children serialized back to markup, e.g. for XHTML content.
+    pub fn inner_xml(&self) -> String {
+        let mut xml = String::new();
+        for node in &self.children {
+            match node {
+                Node::Text(value) => xml.push_str(&escape(value)),
+                Node::Element(element) => element.write(&mut xml),
+            }
+        }
+        xml
+    }
+
+    fn write(&self, xml: &mut String) {
+        let name = self.local_name();
+        xml.push('<');
+        xml.push_str(name);
+        for (attr, value) in &self.attributes {
+            if attr == "xmlns" || attr.starts_with("xmlns:") {
+                continue;
+            }
+            xml.push_str(&format!(" {}=\"{}\"", local_name(attr), escape(value)));
+        }
+        xml.push('>');
+        xml.push_str(&self.inner_xml());
+        xml.push_str(&format!("</{}>", name));
+    }
+
+    pub fn is_named(&self, name: &str) -> bool {
+        if name.contains(':') {
+            self.name == name
+        } else {
+            self.local_name() == name
+        }
+    }
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+struct Reader<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn name(&mut self) -> String {
+        let rest = &self.input[self.pos..];
+        let len = rest
+            .find(|c: char| c.is_whitespace() || c == '/' || c == '>' || c == '=')
+            .unwrap_or(rest.len());
+        self.pos += len;
+        rest[..len].to_string()
+    }
+
+    fn attributes(&mut self) -> Result<(Vec<(String, String)>, bool), XmlError> {
+        let mut attributes = vec![];
+        loop {
+            self.skip_whitespace();
+            let rest = &self.input[self.pos..];
+            if rest.is_empty() {
+                return Err(XmlError("unexpected end of document".to_string()));
+            }
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                return Ok((attributes, true));
+            }
+            if rest.starts_with('>') {
+                self.pos += 1;
+                return Ok((attributes, false));
+            }
+            let name = self.name();
+            if name.is_empty() {
+                // Stray character, e.g. a lone `/`.
+                self.pos += rest.chars().next().map(char::len_utf8).unwrap_or(1);
+                continue;
+            }
+            self.skip_whitespace();
+            let mut value = String::new();
+            if self.input[self.pos..].starts_with('=') {
+                self.pos += 1;
+                self.skip_whitespace();
+                value = self.value();
+            }
+            attributes.push((name, value));
+        }
+    }
+
+    fn value(&mut self) -> String {
+        let rest = &self.input[self.pos..];
+        match rest.chars().next() {
+            Some(quote) if quote == '"' || quote == '\'' => {
+                let len = rest[1..].find(quote).unwrap_or(rest.len() - 1);
+                self.pos += (len + 2).min(rest.len());
+                decode_entities(&rest[1..1 + len])
+            }
+            _ => {
+                let len = rest
+                    .find(|c: char| c.is_whitespace() || c == '>')
+                    .unwrap_or(rest.len());
+                self.pos += len;
+                decode_entities(&rest[..len])
+            }
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(rest.len());
+    }
+
+    fn skip_past(&mut self, pattern: &str) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest
+            .find(pattern)
+            .map(|index| index + pattern.len())
+            .unwrap_or(rest.len());
+    }
+
+    /// Skips a `<!DOCTYPE ...>` including an internal subset in brackets.
+    fn skip_doctype(&mut self) {
+        let rest = &self.input[self.pos..];
+        let mut depth = 0;
+        for (index, c) in rest.char_indices() {
+            match c {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                '>' if depth <= 0 => {
+                    self.pos += index + 1;
+                    return;
+                }
+                _ => {}
+            }
+        }
+        self.pos = self.input.len();
+    }
+}
+
+/// Escapes text for use in element content and attribute values.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}